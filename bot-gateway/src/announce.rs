@@ -0,0 +1,268 @@
+use chrono::{DateTime, Utc};
+use common::{slots_from_cell, slots_label};
+use poise::serenity_prelude as serenity;
+use redis::{AsyncCommands, Client as RedisClient};
+use std::time::Duration;
+use serenity::all::{ChannelId, CreateEmbed, CreateEmbedFooter, CreateMessage, RoleId};
+
+use crate::cache::{fetch_sheet_data, quest_is_published, quest_prerequisites, quest_proof_kinds, quest_step_count, quest_team_size, read_guild_config};
+use crate::models::{GuildConfig, NotificationCategory, ProofKind, QuestPayload};
+use crate::notify::notify_subscribers;
+use crate::scheduler::JobContext;
+use crate::{Error, HubType};
+
+/// Scheduled quests older than this are not announced late (e.g. after a long outage).
+const MAX_ANNOUNCE_DELAY_SECS: i64 = 24 * 3600;
+
+/// How long a command waits for the worker to write a quest before leaving the
+/// announcement to the scheduled job.
+const PUBLISH_WAIT_SECS: u64 = 10;
+
+/// Rebuilds a quest payload from a Quests row, for previews and scheduled announcements.
+pub fn quest_payload_from_row(row: &[String]) -> QuestPayload {
+    // column mapping (0-based):
+    // 0: quest_id, 1: title, 2: category, 3: slots, 4: organizer_name, 5: schedule,
+    // 6: platform, 7: description, 8: deadline, 9: created_at, 10: cloned_from, 11: status,
+    // 12: publish_at, 13: team_size, 14: prerequisites, 15: steps, 16: proof_kinds, 17: creator_id, 20: season
+    let cell = |i: usize| row.get(i).cloned().unwrap_or_default();
+    let cloned_from = cell(10);
+    let publish_at = cell(12);
+    QuestPayload {
+        quest_id: cell(0),
+        title: cell(1),
+        description: cell(7),
+        category: cell(2),
+        organizer_name: cell(4),
+        slots: slots_from_cell(&cell(3)),
+        schedule: cell(5),
+        platform: cell(6),
+        deadline: cell(8),
+        creator_id: cell(17),
+        cloned_from: if cloned_from.is_empty() { None } else { Some(cloned_from) },
+        status: cell(11),
+        team_size: quest_team_size(row),
+        prerequisites: quest_prerequisites(row),
+        steps: quest_step_count(row),
+        proof_kinds: quest_proof_kinds(row),
+        season: cell(20),
+        publish_at: if publish_at.is_empty() { None } else { Some(publish_at) },
+    }
+}
+
+pub fn announcement_embed(payload: &QuestPayload) -> CreateEmbed {
+    let ts = |s: &str| DateTime::parse_from_rfc3339(s).map(|dt| dt.timestamp()).unwrap_or(0);
+
+    let mut embed = CreateEmbed::default()
+        .title(format!("⚔️ New Quest: {}", payload.title))
+        .description(&payload.description)
+        .field("📁 Category", &payload.category, true)
+        .field("🛡️ By", &payload.organizer_name, true)
+        .field("👥 Slots", slots_label(payload.slots), true)
+        .field("📅 Start Time", format!("<t:{}:f>", ts(&payload.schedule)), true)
+        .field("⏰ Deadline", format!("<t:{}:f>", ts(&payload.deadline)), true)
+        .field("📍 Location", &payload.platform, true)
+        .field("ID", &payload.quest_id, false)
+        .color(0xF1C40F)
+        .footer(CreateEmbedFooter::new("Use /take <id> to take the quest"));
+
+    if payload.team_size > 1 {
+        embed = embed.field("🤝 Team Quest", format!("Teams of up to {}. Use /party take <id>", payload.team_size), false);
+    }
+    if !payload.proof_kinds.is_empty() && payload.proof_kinds != [ProofKind::Image] {
+        let kinds: Vec<&str> = payload.proof_kinds.iter().map(|k| k.label()).collect();
+        embed = embed.field("📎 Proof", kinds.join(", "), false);
+    }
+    if payload.steps > 1 {
+        embed = embed.field("🪜 Steps", format!("{} checkpoints, each with its own proof", payload.steps), false);
+    }
+    if !payload.prerequisites.is_empty() {
+        embed = embed.field("🔗 Requires", payload.prerequisites.join(", "), false);
+    }
+    if let Some(source_id) = &payload.cloned_from {
+        embed = embed.field("🧬 Cloned From", source_id, false);
+    }
+    embed
+}
+
+/// Records that a quest has been announced. Returns `false` if it already was,
+/// so the scheduler and `/quest publish` never announce the same quest twice.
+pub async fn mark_announced(redis_client: &RedisClient, quest_id: &str) -> Result<bool, Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let opts = redis::SetOptions::default()
+        .conditional_set(redis::ExistenceCheck::NX)
+        .with_expiration(redis::SetExpiry::EX(7 * 24 * 3600));
+    let set: Option<String> = con.set_options(format!("quest_announced:{}", quest_id), "1", opts).await?;
+    Ok(set.is_some())
+}
+
+/// Posts the announcement in the configured channel and pings new-quest subscribers.
+/// Returns the channel it was posted in, or `None` when no announcement channel is set.
+pub async fn announce_quest(
+    http: &serenity::Http,
+    redis_client: &RedisClient,
+    config: &GuildConfig,
+    default_ping_role: RoleId,
+    payload: &QuestPayload,
+) -> Result<Option<u64>, Error> {
+    let ping_role = config.ping_role_id.map(RoleId::new).unwrap_or(default_ping_role);
+    let display_ts = DateTime::parse_from_rfc3339(&payload.schedule).map(|dt| dt.timestamp()).unwrap_or(0);
+
+    if let Some(channel_id) = config.announcement_channel_id {
+        ChannelId::new(channel_id).send_message(http,
+            CreateMessage::new()
+                .content(format!("<@&{}> A new quest is available!", ping_role))
+                .embed(announcement_embed(payload))
+        ).await?;
+    }
+
+    notify_subscribers(
+        http,
+        redis_client,
+        config,
+        NotificationCategory::NewQuests,
+        &format!("⚔️ New quest **{}** by {} starts <t:{}:f>. Use `/take {}` to join!", payload.title, payload.organizer_name, display_ts, payload.quest_id),
+    ).await;
+
+    Ok(config.announcement_channel_id)
+}
+
+/// One line per quest for a batch announcement, cut to fit an embed description
+/// with a "…and N more" note.
+pub fn batch_summary(payloads: &[QuestPayload]) -> String {
+    const MAX_LEN: usize = 4000;
+    let mut summary = String::new();
+    for (i, payload) in payloads.iter().enumerate() {
+        let display_ts = DateTime::parse_from_rfc3339(&payload.schedule).map(|dt| dt.timestamp()).unwrap_or(0);
+        let line = format!("• **{}** — starts <t:{}:f> — `{}`\n", payload.title, display_ts, payload.quest_id);
+        if summary.len() + line.len() > MAX_LEN - 32 {
+            summary.push_str(&format!("…and {} more", payloads.len() - i));
+            break;
+        }
+        summary.push_str(&line);
+    }
+    summary.trim_end().to_string()
+}
+
+/// Announces many quests at once (e.g. an import) with a single post and role ping,
+/// and one message to new-quest subscribers. Returns the channel it was posted in,
+/// or `None` when no announcement channel is set.
+pub async fn announce_quest_batch(
+    http: &serenity::Http,
+    redis_client: &RedisClient,
+    config: &GuildConfig,
+    default_ping_role: RoleId,
+    payloads: &[QuestPayload],
+) -> Result<Option<u64>, Error> {
+    let ping_role = config.ping_role_id.map(RoleId::new).unwrap_or(default_ping_role);
+    for payload in payloads {
+        mark_announced(redis_client, &payload.quest_id).await?;
+    }
+
+    if let Some(channel_id) = config.announcement_channel_id {
+        let embed = CreateEmbed::default()
+            .title(format!("⚔️ {} New Quests", payloads.len()))
+            .description(batch_summary(payloads))
+            .color(0xF1C40F)
+            .footer(CreateEmbedFooter::new("Use /view <id> for details and /take <id> to take a quest"));
+        ChannelId::new(channel_id).send_message(http,
+            CreateMessage::new()
+                .content(format!("<@&{}> {} new quests are available!", ping_role, payloads.len()))
+                .embed(embed)
+        ).await?;
+    }
+
+    let titles: Vec<String> = payloads.iter().take(10).map(|p| format!("**{}**", p.title)).collect();
+    let more = payloads.len().saturating_sub(titles.len());
+    notify_subscribers(
+        http,
+        redis_client,
+        config,
+        NotificationCategory::NewQuests,
+        &format!("⚔️ {} new quests were posted: {}{}. Use `/list` to see them.",
+            payloads.len(), titles.join(", "), if more > 0 { format!(" and {} more", more) } else { String::new() }),
+    ).await;
+
+    Ok(config.announcement_channel_id)
+}
+
+/// Waits until the Quests sheet shows the quest as published, reading the sheet
+/// directly since the cache can be a minute behind. Announcing earlier lets members
+/// try to take a quest the worker hasn't written yet.
+pub async fn wait_until_published(hub: &HubType, sheet_id: &str, quest_id: &str) -> bool {
+    for _ in 0..PUBLISH_WAIT_SECS {
+        match hub.spreadsheets().values_get(sheet_id, "Quests!A:L").doit().await {
+            Ok((_, range)) => {
+                let published = range.values.unwrap_or_default().iter().any(|row| {
+                    let row: Vec<String> = row.iter().map(|cell| cell.as_str().unwrap_or("").to_string()).collect();
+                    row.first().map(|s| s.as_str()) == Some(quest_id) && quest_is_published(&row)
+                });
+                if published {
+                    return true;
+                }
+            }
+            Err(e) => eprintln!("Failed to read Quests sheet while waiting for {}: {:?}", quest_id, e),
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    false
+}
+
+/// Announces published quests whose publish time (column M) has passed. Scheduled
+/// quests wait until the worker has flipped them to PUBLISHED.
+pub async fn announce_due_quests(job: &JobContext) -> Result<(), Error> {
+    let data = fetch_sheet_data(&job.redis_client, &job.sheets_hub, &job.google_sheet_id).await?;
+    let now = Utc::now().timestamp();
+    let mut config: Option<GuildConfig> = None;
+
+    for row in data.q_rows.iter().skip(1) {
+        if !quest_is_published(row) {
+            continue;
+        }
+        let Some(publish_at) = row.get(12).and_then(|s| DateTime::parse_from_rfc3339(s).ok()) else {
+            continue;
+        };
+        let age = now - publish_at.timestamp();
+        if !(0..MAX_ANNOUNCE_DELAY_SECS).contains(&age) {
+            continue;
+        }
+
+        let payload = quest_payload_from_row(row);
+        if !mark_announced(&job.redis_client, &payload.quest_id).await? {
+            continue;
+        }
+
+        if config.is_none() {
+            config = Some(read_guild_config(&job.redis_client, job.guild_id.get()).await?);
+        }
+        let config = config.as_ref().unwrap();
+        if let Err(e) = announce_quest(&job.http, &job.redis_client, config, job.participant_role_id, &payload).await {
+            eprintln!("Failed to announce scheduled quest {}: {:?}", payload.quest_id, e);
+        } else {
+            println!("📣 Announced scheduled quest {}", payload.quest_id);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quest(id: usize) -> QuestPayload {
+        let row: Vec<String> = vec![format!("q{}", id), format!("Quest {}", id), String::new(), "10".to_string(), String::new(), "2025-11-21T19:00:00+07:00".to_string()];
+        quest_payload_from_row(&row)
+    }
+
+    #[test]
+    fn test_batch_summary_fits_embed() {
+        let few: Vec<QuestPayload> = (0..3).map(quest).collect();
+        let summary = batch_summary(&few);
+        assert_eq!(summary.lines().count(), 3);
+        assert!(summary.starts_with("• **Quest 0** — starts <t:1763726400:f> — `q0`"));
+
+        let many: Vec<QuestPayload> = (0..100).map(quest).collect();
+        let summary = batch_summary(&many);
+        assert!(summary.len() <= 4000);
+        assert!(summary.ends_with("more"));
+    }
+}
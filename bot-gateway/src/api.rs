@@ -1,9 +1,10 @@
 use axum::{
-    routing::post,
+    routing::{get, post},
     Router,
     Json,
-    extract::State,
-    http::StatusCode,
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
 };
 use rdkafka::producer::{FutureProducer, FutureRecord};
 use rdkafka::util::Timeout;
@@ -13,10 +14,30 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use redis::{Client as RedisClient, AsyncCommands};
 
+use crate::cache::read_guild_config;
+use crate::commands::season::resolve_season;
+use crate::export::{build_export, export_filename, ExportFilter};
+use crate::HubType;
+
 #[derive(Clone)]
 pub struct ApiState {
     pub producer: FutureProducer,
     pub redis_client: RedisClient,
+    pub sheets_hub: HubType,
+    pub google_sheet_id: String,
+    /// Guild whose config (seasons) scopes exports.
+    pub guild_id: u64,
+    /// Bearer token for `GET /api/export`; the endpoint is disabled when unset.
+    pub export_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub division: Option<String>,
+    /// Season id, or `all`; defaults to the active season.
+    pub season: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -60,12 +81,49 @@ async fn invalidate_cache_handler(
     Ok("Cache cleared".to_string())
 }
 
-pub async fn start_server(producer: FutureProducer, addr: SocketAddr, redis_client: RedisClient) {
-    let shared_state = Arc::new(ApiState { producer, redis_client });
+// Handler for GET /api/export?from=YYYY-MM-DD&to=YYYY-MM-DD&division=Illust&season=GST25
+async fn export_handler(
+    State(state): State<Arc<ApiState>>,
+    headers: HeaderMap,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let Some(token) = &state.export_token else {
+        return Err((StatusCode::NOT_FOUND, "Export is disabled".to_string()));
+    };
+    let authorized = headers.get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|v| v == token);
+    if !authorized {
+        return Err((StatusCode::UNAUTHORIZED, "Missing or wrong bearer token".to_string()));
+    }
+
+    let mut filter = ExportFilter::parse(query.from.as_deref(), query.to.as_deref(), query.division.as_deref())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let config = read_guild_config(&state.redis_client, state.guild_id).await.unwrap_or_default();
+    filter.season = resolve_season(&config, query.season.as_deref()).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let archive = build_export(&state.redis_client, &state.sheets_hub, &state.google_sheet_id, &filter).await
+        .map_err(|e| {
+            eprintln!("Export failed: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Export failed".to_string())
+        })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", export_filename())),
+        ],
+        archive,
+    ))
+}
+
+pub async fn start_server(state: ApiState, addr: SocketAddr) {
+    let shared_state = Arc::new(state);
 
     let app = Router::new()
         .route("/api/submit", post(submit_handler))
         .route("/api/invalidate_cache", post(invalidate_cache_handler))
+        .route("/api/export", get(export_handler))
         .with_state(shared_state);
 
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
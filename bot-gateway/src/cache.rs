@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use redis::AsyncCommands;
 use serde_json::from_str;
-use crate::{Data, Error, models::GuildConfig};
+use redis::Client as RedisClient;
+use crate::{Data, Error, HubType, models::{GuildConfig, NotificationCategory, NotificationPrefs, Party, ProofKind}};
 
 type Context<'a> = poise::Context<'a, Data, Error>;
 
@@ -10,10 +11,83 @@ pub struct CachedQuestData {
     pub q_rows: Vec<Vec<String>>,
     pub p_rows: Vec<Vec<String>>,
     pub c_rows: Vec<Vec<String>>,
+    #[serde(default)]
+    pub t_rows: Vec<Vec<String>>,
+}
+
+// Quests tab column mapping (0-based):
+// 0: quest_id, 1: title, 2: category, 3: slots, 4: organizer_name, 5: schedule,
+// 6: platform, 7: description, 8: deadline, 9: created_at, 10: cloned_from,
+// 11: status (PUBLISHED/DRAFT/SCHEDULED, empty = PUBLISHED), 12: publish_at,
+// 13: team_size (empty = solo), 14: prerequisites (comma-separated quest ids),
+// 15: steps (empty = single step), 16: proof_kinds (comma-separated, empty = IMAGE),
+// 17: creator_id (Discord user id; empty for quests created before it was recorded),
+// 18: voice_channel_id (empty = no voice attendance), 19: voice_minutes,
+// 20: season (id of the season active at creation; empty = placed by created_at)
+//
+// Participants tab column mapping (0-based):
+// 0: quest_id, 1: user_id, 2: user_tag, 3: status, 4: timestamp (taken at), 5: team_id,
+// 6: checked_in_at (written by the worker)
+
+/// Discord id of the member who created the quest, if recorded.
+pub fn quest_creator(row: &[String]) -> Option<&str> {
+    row.get(17).map(|s| s.as_str()).filter(|s| !s.is_empty())
+}
+
+/// Voice channel whose presence counts as attendance for the quest, if bound.
+pub fn quest_voice_channel(row: &[String]) -> Option<u64> {
+    row.get(18).and_then(|s| s.parse::<u64>().ok())
+}
+
+/// Minutes in the bound voice channel needed to complete the quest.
+pub fn quest_voice_minutes(row: &[String]) -> u32 {
+    row.get(19).and_then(|s| s.parse::<u32>().ok()).unwrap_or(crate::voice::DEFAULT_VOICE_MINUTES).max(1)
+}
+
+/// Season id the quest was stamped with at creation, if any.
+pub fn quest_season(row: &[String]) -> Option<&str> {
+    row.get(20).map(|s| s.as_str()).filter(|s| !s.is_empty())
+}
+
+/// Whether a quest row is visible to members (`/list`, autocomplete, `/take`).
+/// Drafts and quests waiting for their publish time are hidden.
+pub fn quest_is_published(row: &[String]) -> bool {
+    !matches!(row.get(11).map(|s| s.as_str()), Some("DRAFT") | Some("SCHEDULED"))
+}
+
+/// Quest ids that must be COMPLETED or VERIFIED before this quest can be taken.
+pub fn quest_prerequisites(row: &[String]) -> Vec<String> {
+    row.get(14)
+        .map(|s| s.split(',').map(|id| id.trim().to_string()).filter(|id| !id.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Number of checkpoints in a quest row; 1 for single-step quests.
+pub fn quest_step_count(row: &[String]) -> i8 {
+    row.get(15).and_then(|s| s.parse::<i8>().ok()).unwrap_or(1).max(1)
+}
+
+/// Proof kinds a quest row accepts; images only when none are listed.
+pub fn quest_proof_kinds(row: &[String]) -> Vec<ProofKind> {
+    let kinds: Vec<ProofKind> = row.get(16)
+        .map(|s| s.split(',').filter_map(ProofKind::parse).collect())
+        .unwrap_or_default();
+    if kinds.is_empty() { vec![ProofKind::Image] } else { kinds }
+}
+
+/// Members per team for a quest row; 1 for solo quests.
+pub fn quest_team_size(row: &[String]) -> i8 {
+    row.get(13).and_then(|s| s.parse::<i8>().ok()).unwrap_or(1).max(1)
 }
 
 pub async fn get_cached_sheet_data(ctx: Context<'_>) -> Result<CachedQuestData, Error> {
-    let redis_client = &ctx.data().redis_client;
+    let data = ctx.data();
+    fetch_sheet_data(&data.redis_client, &data.sheets_hub, &data.google_sheet_id).await
+}
+
+/// Reads the Quests / Participants / Communities / Templates tabs through the Redis cache.
+/// Usable outside of a command (e.g. from background jobs).
+pub async fn fetch_sheet_data(redis_client: &RedisClient, hub: &HubType, sheet_id: &str) -> Result<CachedQuestData, Error> {
     let mut con = redis_client.get_multiplexed_async_connection().await?;
     let cache_key = "sheet_data_cache";
 
@@ -25,13 +99,11 @@ pub async fn get_cached_sheet_data(ctx: Context<'_>) -> Result<CachedQuestData,
         }
     }
 
-    let hub = &ctx.data().sheets_hub;
-    let sheet_id = &ctx.data().google_sheet_id;
-
     let result = hub.spreadsheets().values_batch_get(sheet_id)
-        .add_ranges("Quests!A:I")
-        .add_ranges("Participants!A:D")
+        .add_ranges("Quests!A:U")
+        .add_ranges("Participants!A:G")
         .add_ranges("Communities!A:B")
+        .add_ranges("Templates!A:N")
         .doit()
         .await?;
 
@@ -52,6 +124,7 @@ pub async fn get_cached_sheet_data(ctx: Context<'_>) -> Result<CachedQuestData,
         q_rows: extract_rows(0),
         p_rows: extract_rows(1),
         c_rows: extract_rows(2),
+        t_rows: extract_rows(3),
     };
 
     let json_str = serde_json::to_string(&data)?;
@@ -60,8 +133,23 @@ pub async fn get_cached_sheet_data(ctx: Context<'_>) -> Result<CachedQuestData,
     Ok(data)
 }
 
+/// Reads the Submissions tab straight from the sheet. It isn't cached since it is
+/// only needed for per-user progress, not on every autocomplete.
+pub async fn fetch_submission_rows(hub: &HubType, sheet_id: &str) -> Result<Vec<Vec<String>>, Error> {
+    // Submissions column mapping (0-based):
+    // 0: quest_id, 1: user_id, 2: proof (URL or text), 3: timestamp, 4: step, 5: proof_kind, 6: attempt,
+    // 7: review_status (empty = pending), 8: reviewer_id, 9: reviewed_at, 10: review_reason
+    let (_, range) = hub.spreadsheets().values_get(sheet_id, "Submissions!A:K").doit().await?;
+    Ok(range.values.unwrap_or_default().iter()
+        .map(|row| row.iter().map(|cell| cell.as_str().unwrap_or("").to_string()).collect())
+        .collect())
+}
+
 pub async fn get_guild_config(ctx: Context<'_>, guild_id: u64) -> Result<GuildConfig, Error> {
-    let redis_client = &ctx.data().redis_client;
+    read_guild_config(&ctx.data().redis_client, guild_id).await
+}
+
+pub async fn read_guild_config(redis_client: &RedisClient, guild_id: u64) -> Result<GuildConfig, Error> {
     let mut con = redis_client.get_multiplexed_async_connection().await?;
     let cache_key = format!("guild_config:{}", guild_id);
     let cached: Option<String> = con.get(&cache_key).await.ok();
@@ -81,4 +169,80 @@ pub async fn set_guild_config(ctx: Context<'_>, guild_id: u64, config: &GuildCon
     let json = serde_json::to_string(config)?;
     let _: () = con.set(&cache_key, json).await?;
     Ok(())
-}
\ No newline at end of file
+}
+pub async fn get_notification_prefs(redis_client: &RedisClient, user_id: &str) -> Result<NotificationPrefs, Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let cache_key = format!("notify_prefs:{}", user_id);
+    let cached: Option<String> = con.get(&cache_key).await.ok();
+
+    if let Some(json) = cached {
+        if let Ok(prefs) = serde_json::from_str::<NotificationPrefs>(&json) {
+            return Ok(prefs);
+        }
+    }
+    Ok(NotificationPrefs::default())
+}
+
+/// Stores a user's preferences and keeps the per-category subscriber sets
+/// (`notify_subscribers:<category>`) used for opt-in broadcasts in sync.
+pub async fn set_notification_prefs(redis_client: &RedisClient, user_id: &str, prefs: &NotificationPrefs) -> Result<(), Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let cache_key = format!("notify_prefs:{}", user_id);
+    let json = serde_json::to_string(prefs)?;
+    let _: () = con.set(&cache_key, json).await?;
+
+    for category in NotificationCategory::ALL {
+        let set_key = format!("notify_subscribers:{}", category.key());
+        if prefs.is_enabled(category) {
+            let _: () = con.sadd(&set_key, user_id).await?;
+        } else {
+            let _: () = con.srem(&set_key, user_id).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Users who explicitly saved preferences with `category` enabled.
+pub async fn get_category_subscribers(redis_client: &RedisClient, category: NotificationCategory) -> Result<Vec<String>, Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let set_key = format!("notify_subscribers:{}", category.key());
+    let members: Vec<String> = con.smembers(&set_key).await?;
+    Ok(members)
+}
+
+/// The party a user currently belongs to, if any.
+pub async fn get_user_party(redis_client: &RedisClient, user_id: &str) -> Result<Option<Party>, Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let party_id: Option<String> = con.get(format!("party_member:{}", user_id)).await?;
+    let Some(party_id) = party_id else {
+        return Ok(None);
+    };
+    get_party(redis_client, &party_id).await
+}
+
+pub async fn get_party(redis_client: &RedisClient, party_id: &str) -> Result<Option<Party>, Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let json: Option<String> = con.get(format!("party:{}", party_id)).await?;
+    Ok(json.and_then(|j| serde_json::from_str::<Party>(&j).ok()))
+}
+
+/// Stores a party and points every member's `party_member:<user>` key at it.
+pub async fn save_party(redis_client: &RedisClient, party: &Party) -> Result<(), Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let json = serde_json::to_string(party)?;
+    let _: () = con.set(format!("party:{}", party.party_id), json).await?;
+    for member in &party.members {
+        let _: () = con.set(format!("party_member:{}", member.user_id), &party.party_id).await?;
+    }
+    Ok(())
+}
+
+/// Removes a party and frees its members to join another one.
+pub async fn delete_party(redis_client: &RedisClient, party: &Party) -> Result<(), Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let _: () = con.del(format!("party:{}", party.party_id)).await?;
+    for member in &party.members {
+        let _: () = con.del(format!("party_member:{}", member.user_id)).await?;
+    }
+    Ok(())
+}
@@ -1,8 +1,12 @@
-use crate::cache::get_cached_sheet_data;
+use crate::cache::{get_cached_sheet_data, get_guild_config};
+use crate::commands::season::{resolve_season, season_label};
 use crate::{Data, Error};
-use crate::models::NewCommunityPayload;
+use crate::models::{Division, NewCommunityPayload};
 use crate::kafka::produce_event;
+use crate::export::{build_export, export_filename, ExportFilter};
 use common::normalize_name;
+use poise::CreateReply;
+use serenity::all::CreateAttachment;
 
 type Context<'a> = poise::Context<'a, Data, Error>;
 
@@ -52,6 +56,42 @@ pub async fn register_community(
     Ok(())
 }
 
+#[poise::command(slash_command, description_localized("en-US", "Export quests, participants, submissions and communities as a zip"),
+                 check = "crate::security::check_admin")]
+pub async fn export(
+    ctx: Context<'_>,
+    #[description = "Quests starting on or after (YYYY-MM-DD, WIB)"] from: Option<String>,
+    #[description = "Quests starting on or before (YYYY-MM-DD, WIB)"] to: Option<String>,
+    #[description = "Only quests of this division"] division: Option<Division>,
+    #[description = "Season ID, or \"all\" (default: the active season)"]
+    #[autocomplete = "crate::commands::season::autocomplete_season"]
+    season: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    let division = division.filter(|d| !matches!(d, Division::None)).map(|d| format!("{:?}", d));
+    let filter = ExportFilter::parse(from.as_deref(), to.as_deref(), division.as_deref())
+        .and_then(|filter| Ok(ExportFilter { season: resolve_season(&config, season.as_deref())?, ..filter }));
+    let filter = match filter {
+        Ok(filter) => filter,
+        Err(msg) => {
+            ctx.say(format!("❌ {}", msg)).await?;
+            return Ok(());
+        }
+    };
+
+    let data = ctx.data();
+    let archive = build_export(&data.redis_client, &data.sheets_hub, &data.google_sheet_id, &filter).await?;
+    ctx.send(CreateReply::default()
+        .content(format!("📦 Quests, participants, submissions and communities as CSV and JSON{}.", season_label(filter.season.as_ref())))
+        .attachment(CreateAttachment::bytes(archive, export_filename()))
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,4 +111,4 @@ mod tests {
         let res = determine_leader_id(Some(&user));
         assert_eq!(res, "12345");
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,229 @@
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude as serenity;
+use redis::AsyncCommands;
+use serenity::all::{
+    ButtonStyle, ChannelId, ComponentInteraction, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, EditInteractionResponse,
+};
+
+use crate::cache::{fetch_sheet_data, get_cached_sheet_data, get_guild_config, quest_creator, read_guild_config};
+use crate::kafka::send_event;
+use crate::models::CheckInPayload;
+use crate::{Data, Error};
+
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+/// Check-in opens this many minutes before a quest starts and closes as long after,
+/// unless changed with `/config set_checkin_window`.
+pub const DEFAULT_CHECKIN_WINDOW_MINUTES: u32 = 15;
+
+/// Custom id prefix of the Check In button; the quest id follows it.
+pub const CHECKIN_BUTTON_PREFIX: &str = "checkin:";
+
+/// Unix timestamps (start, end) of the check-in window around a quest's start.
+pub fn checkin_window(schedule: i64, window_minutes: u32) -> (i64, i64) {
+    let margin = window_minutes as i64 * 60;
+    (schedule - margin, schedule + margin)
+}
+
+pub fn checkin_open(now: i64, schedule: i64, window_minutes: u32) -> bool {
+    let (start, end) = checkin_window(schedule, window_minutes);
+    now >= start && now <= end
+}
+
+/// Normalizes a typed check-in code so `ab12cd` matches `AB12CD`.
+fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+fn code_key(quest_id: &str) -> String {
+    format!("checkin_code:{}", quest_id)
+}
+
+fn schedule_ts(row: &[String]) -> Option<i64> {
+    row.get(5).and_then(|s| DateTime::parse_from_rfc3339(s).ok()).map(|dt| dt.timestamp())
+}
+
+/// Checks a member in to a quest they took. Shared by `/checkin` and the Check In button,
+/// so it works from the bot's `Data` rather than a command context.
+async fn check_in(data: &Data, guild_id: u64, quest_id: &str, user_id: &str) -> Result<String, Error> {
+    let sheet = fetch_sheet_data(&data.redis_client, &data.sheets_hub, &data.google_sheet_id).await?;
+    let Some(row) = sheet.q_rows.iter().skip(1).find(|row| row.len() >= 9 && row[0] == quest_id) else {
+        return Ok(format!("❌ Quest ID `{}` not found.", quest_id));
+    };
+    let title = &row[1];
+
+    let status = sheet.p_rows.iter().skip(1)
+        .find(|p| p.len() >= 4 && p[0] == quest_id && p[1] == user_id)
+        .map(|p| p[3].as_str());
+    match status {
+        Some("ON_PROGRESS") => {}
+        Some("CHECKED_IN") => return Ok(format!("✅ You're already checked in to **{}**.", title)),
+        Some(status) => return Ok(format!("❌ Your status on **{}** is `{}`.", title, status)),
+        None => return Ok(format!("❌ You haven't taken **{}**.", title)),
+    }
+
+    let Some(schedule) = schedule_ts(row) else {
+        return Ok(format!("❌ **{}** has no start time yet.", title));
+    };
+    let config = read_guild_config(&data.redis_client, guild_id).await.unwrap_or_default();
+    let window = config.checkin_window_minutes.unwrap_or(DEFAULT_CHECKIN_WINDOW_MINUTES);
+    if !checkin_open(Utc::now().timestamp(), schedule, window) {
+        let (start, end) = checkin_window(schedule, window);
+        return Ok(format!("⏰ Check-in for **{}** is open from <t:{}:t> to <t:{}:t>.", title, start, end));
+    }
+
+    send_event(&data.kafka_producer, "CHECK_IN", &CheckInPayload {
+        quest_id: quest_id.to_string(),
+        user_id: user_id.to_string(),
+    }).await?;
+    Ok(format!("📍 Checked in to **{}**. Have fun!", title))
+}
+
+/// Handles a press of a Check In button posted by `/attendance`.
+pub async fn handle_checkin_button(ctx: &serenity::Context, interaction: &ComponentInteraction, data: &Data) -> Result<(), Error> {
+    let quest_id = interaction.data.custom_id.trim_start_matches(CHECKIN_BUTTON_PREFIX);
+    let guild_id = interaction.guild_id.unwrap_or(data.target_guild_id);
+
+    // Acknowledge first: a cold sheet cache plus the Kafka send can take longer than
+    // Discord's 3-second limit.
+    interaction.create_response(&ctx.http, CreateInteractionResponse::Defer(
+        CreateInteractionResponseMessage::new().ephemeral(true)
+    )).await?;
+
+    let content = match check_in(data, guild_id.get(), quest_id, &interaction.user.id.to_string()).await {
+        Ok(content) => content,
+        Err(e) => {
+            interaction.edit_response(&ctx.http, EditInteractionResponse::new().content("❌ Check-in failed. Please try again.")).await?;
+            return Err(e);
+        }
+    };
+    interaction.edit_response(&ctx.http, EditInteractionResponse::new().content(content)).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Check in to a live quest with the organizer's code"),
+                 check = "crate::security::check_guild")]
+pub async fn checkin(
+    ctx: Context<'_>,
+    #[description = "Code announced by the organizer"] code: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let user_id = ctx.author().id.to_string();
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let data = get_cached_sheet_data(ctx).await?;
+    let taken: Vec<&String> = data.p_rows.iter().skip(1)
+        .filter(|p| p.len() >= 4 && p[1] == user_id && (p[3] == "ON_PROGRESS" || p[3] == "CHECKED_IN"))
+        .map(|p| &p[0])
+        .collect();
+
+    let code = normalize_code(&code);
+    let mut con = ctx.data().redis_client.get_multiplexed_async_connection().await?;
+    let mut quest_id = None;
+    for id in taken {
+        let expected: Option<String> = con.get(code_key(id)).await?;
+        if expected.as_deref() == Some(code.as_str()) {
+            quest_id = Some(id.clone());
+            break;
+        }
+    }
+
+    let Some(quest_id) = quest_id else {
+        ctx.say("❌ That code doesn't match any open check-in for a quest you took.").await?;
+        return Ok(());
+    };
+
+    let content = check_in(ctx.data(), guild_id.get(), &quest_id, &user_id).await?;
+    ctx.say(content).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Open check-in for a quest and get a code to announce"),
+                 check = "crate::security::check_guild")]
+pub async fn attendance(
+    ctx: Context<'_>,
+    #[description = "Quest"]
+    #[autocomplete = "crate::commands::quest::autocomplete_quest_id"]
+    quest_id: String,
+    #[description = "Also post a Check In button in the announcement channel (default: yes)"]
+    post_button: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let Some(row) = data.q_rows.iter().skip(1).find(|row| row.len() >= 9 && row[0] == quest_id) else {
+        ctx.say(format!("❌ Quest ID `{}` not found.", quest_id)).await?;
+        return Ok(());
+    };
+
+    if quest_creator(row) != Some(ctx.author().id.to_string().as_str()) && !crate::security::is_quest_giver(ctx).await? {
+        ctx.say("⛔ Only the quest's creator or staff with QuestRole can open check-in.").await?;
+        return Ok(());
+    }
+
+    let title = row[1].clone();
+    let Some(schedule) = schedule_ts(row) else {
+        ctx.say(format!("❌ **{}** has no start time, so there's no check-in window.", title)).await?;
+        return Ok(());
+    };
+
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    let window = config.checkin_window_minutes.unwrap_or(DEFAULT_CHECKIN_WINDOW_MINUTES);
+    let (start, end) = checkin_window(schedule, window);
+    let now = Utc::now().timestamp();
+    if now > end {
+        ctx.say(format!("❌ Check-in for **{}** closed <t:{}:R>.", title, end)).await?;
+        return Ok(());
+    }
+
+    // The code lives until the window closes; reopening keeps the code already announced.
+    let mut con = ctx.data().redis_client.get_multiplexed_async_connection().await?;
+    let existing: Option<String> = con.get(code_key(&quest_id)).await?;
+    let code = existing.unwrap_or_else(|| normalize_code(&uuid::Uuid::new_v4().simple().to_string()[..6]));
+    let _: () = con.set_ex(code_key(&quest_id), &code, (end - now).max(1) as u64).await?;
+
+    if post_button.unwrap_or(true) {
+        let channel_id = ChannelId::new(config.announcement_channel_id.unwrap_or(ctx.channel_id().get()));
+        let embed = CreateEmbed::default()
+            .title(format!("📍 Check-In: {}", title))
+            .description(format!("Took this quest? Press **Check In** between <t:{}:t> and <t:{}:t>.", start, end))
+            .field("ID", &quest_id, false)
+            .color(0x1ABC9C)
+            .footer(CreateEmbedFooter::new("Or use /checkin <code> with the code from the organizer"));
+        let button = CreateButton::new(format!("{}{}", CHECKIN_BUTTON_PREFIX, quest_id))
+            .label("Check In")
+            .emoji('📍')
+            .style(ButtonStyle::Success);
+
+        if let Err(e) = channel_id.send_message(ctx, CreateMessage::new()
+            .embed(embed)
+            .components(vec![CreateActionRow::Buttons(vec![button])])
+        ).await {
+            eprintln!("Failed to post check-in button for {}: {:?}", quest_id, e);
+        }
+    }
+
+    ctx.say(format!(
+        "📍 Check-in for **{}** is open from <t:{}:t> to <t:{}:t>.\nAnnounce this code: **`{}`** (`/checkin {}`)",
+        title, start, end, code, code
+    )).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkin_window() {
+        let start = 1_700_000_000;
+        assert_eq!(checkin_window(start, 15), (start - 900, start + 900));
+        assert!(checkin_open(start - 900, start, 15));
+        assert!(checkin_open(start + 900, start, 15));
+        assert!(!checkin_open(start - 901, start, 15));
+        assert!(!checkin_open(start + 901, start, 15));
+        assert_eq!(normalize_code(" ab12cd "), "AB12CD");
+    }
+}
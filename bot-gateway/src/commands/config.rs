@@ -1,13 +1,15 @@
 use poise::CreateReply;
+use chrono::NaiveTime;
+use poise::ChoiceParameter;
 use serenity::all::{Channel, Role};
 
-use crate::{Data, Error, cache::{get_guild_config, set_guild_config}, models::{ChannelConfigType, RoleConfigType}};
+use crate::{Data, Error, cache::{get_guild_config, set_guild_config}, models::{ChannelConfigType, DigestWeekday, RoleConfigType}};
 
 type Context<'a> = poise::Context<'a, Data, Error>;
 
 #[poise::command(
     slash_command, 
-    subcommands("set_channel", "set_role", "view"),
+    subcommands("set_channel", "set_role", "set_digest", "clear_digest", "set_checkin_window", "view"),
     description_localized("en-US", "Configure bot settings"),
     check = "crate::security::check_admin"
 )]
@@ -38,6 +40,10 @@ pub async fn set_channel(
             config.log_channel_id = Some(channel.id().get());
             "Log channel"
         }
+        ChannelConfigType::Notification => {
+            config.notification_thread_id = Some(channel.id().get());
+            "Notification thread"
+        }
     };
     
     set_guild_config(ctx, guild_id.get(), &config).await?;
@@ -105,6 +111,10 @@ pub async fn clear_channel(
             config.log_channel_id = None;
             "Log channel"
         }
+        ChannelConfigType::Notification => {
+            config.notification_thread_id = None;
+            "Notification thread"
+        }
     };
 
     set_guild_config(ctx, guild_id.get(), &config).await?;
@@ -151,6 +161,79 @@ pub async fn clear_role(
     Ok(())
 }
 
+#[poise::command(slash_command, description_localized("en-US", "Schedule quest digests in the announcement channel"))]
+pub async fn set_digest(
+    ctx: Context<'_>,
+    #[description = "Time to post (HH:MM, WIB)"] time: String,
+    #[description = "Post a daily digest"] daily: bool,
+    #[description = "Also post a weekly digest on this day"] weekly_day: Option<DigestWeekday>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let time = time.trim().to_string();
+    if NaiveTime::parse_from_str(&time, "%H:%M").is_err() {
+        ctx.send(CreateReply::default()
+            .content("❌ Wrong time format! Use: HH:MM (E.g: 08:00)")
+            .ephemeral(true)
+        ).await?;
+        return Ok(());
+    }
+
+    let mut config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    config.digest_time = Some(time.clone());
+    config.daily_digest = daily;
+    config.weekly_digest_day = weekly_day;
+
+    set_guild_config(ctx, guild_id.get(), &config).await?;
+
+    let weekly = weekly_day.map(|d| format!("every {}", d.name())).unwrap_or_else(|| "off".to_string());
+    ctx.send(CreateReply::default()
+        .content(format!("✅ Digests at {} WIB — daily: {}, weekly: {}", time, if daily { "on" } else { "off" }, weekly))
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Stop posting quest digests"))]
+pub async fn clear_digest(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let mut config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    config.digest_time = None;
+    config.daily_digest = false;
+    config.weekly_digest_day = None;
+
+    set_guild_config(ctx, guild_id.get(), &config).await?;
+
+    ctx.send(CreateReply::default()
+        .content("✅ Digests disabled")
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Set how long check-in stays open around a quest's start"))]
+pub async fn set_checkin_window(
+    ctx: Context<'_>,
+    #[description = "Minutes before and after the start time"]
+    #[min = 1]
+    #[max = 180]
+    minutes: u32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let mut config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    config.checkin_window_minutes = Some(minutes);
+
+    set_guild_config(ctx, guild_id.get(), &config).await?;
+
+    ctx.send(CreateReply::default()
+        .content(format!("✅ Check-in opens {} minutes before a quest starts and closes {} minutes after", minutes, minutes))
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
 #[poise::command(slash_command, description_localized("en-US", "View current bot configuration"))]
 pub async fn view(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
@@ -165,22 +248,40 @@ pub async fn view(ctx: Context<'_>) -> Result<(), Error> {
         opt.map(|id| format!("<@&{}>", id)).unwrap_or_else(|| default.to_string())
     };
     
+    let digest = match &config.digest_time {
+        Some(time) => format!(
+            "{} WIB — daily: {}, weekly: {}",
+            time,
+            if config.daily_digest { "on" } else { "off" },
+            config.weekly_digest_day.map(|d| d.name().to_string()).unwrap_or_else(|| "off".to_string()),
+        ),
+        None => "Not set (disabled)".to_string(),
+    };
+
     let content = format!(
         "**⚙️ Current Configuration**\n\n\
         **Channels**\n\
         📢 Announcement: {}\n\
         📝 Proof Submission: {}\n\
-        📋 Log: {}\n\n\
+        📋 Log: {}\n\
+        🧵 Notification Thread: {}\n\n\
         **Roles**\n\
         🔔 Ping Role: {}\n\
         🎖️ Quest Giver: {}\n\
-        ✅ Verifier: {}",
+        ✅ Verifier: {}\n\n\
+        **Digests**\n\
+        📰 {}\n\n\
+        **Attendance**\n\
+        📍 Check-in window: ±{} minutes",
         fmt_channel(config.announcement_channel_id, "Not set (command channel)"),
         fmt_channel(config.proof_channel_id, "Not set (command channel)"),
         fmt_channel(config.log_channel_id, "Not set (disabled)"),
+        fmt_channel(config.notification_thread_id, "Not set (DM only)"),
         fmt_role(config.ping_role_id, "Not set (default participant)"),
         fmt_role(config.quest_giver_role_id, "Not set (env default)"),
         fmt_role(config.verifier_role_id, "Not set (admin only)"),
+        digest,
+        config.checkin_window_minutes.unwrap_or(crate::commands::checkin::DEFAULT_CHECKIN_WINDOW_MINUTES),
     );
     
     ctx.send(CreateReply::default()
@@ -15,22 +15,27 @@ pub async fn help(
             .description("Below is a list of all commands available for managing and participating in quests.")
             .field(
                 "⚔️ Quest Management (QuestGiver or Admins)",
-                "`/create` - Open a modal to **create a new quest**.\n`/edit <id>` - Open a modal to **edit** an existing quest.\n`/delete <id>` - **Delete** an existing quest.",
+                "`/create` - Open a modal to **create a new quest** (`draft: True` to save it unpublished).\n`/quest clone <id>` - Open the create modal **pre-filled** from an existing quest.\n`/quest drafts|preview|publish` - Review drafts and **publish** them now or at a set time.\n`/quest voice <id> [channel]` - Count **voice channel time** as attendance.\n`/edit <id>` - Open a modal to **edit** an existing quest.\n`/delete <id>` - **Delete** an existing quest (asks for confirmation and a reason).\n`/roster <id>` - See **participants**, DM them, mark no-shows or export CSV.\n`/attendance <id>` - Open **check-in** and get a code to announce.\n`/template save|schedule|list|pause|resume` - Manage **recurring quest templates**.",
                 false,
             )
             .field(
                 "🗺️ Participant Actions (CaStaff)",
-                "`/take <id>` - **Register** yourself as a participant.\n`/drop <id>` - **Unregister** from a quest (before start).\n`/submit <id> <attachment:image>` - **Submit** image proof for a taken quest.",
+                "`/take <id>` - **Register** yourself as a participant.\n`/party create|invite|join|leave|view|take` - Form a **party** and register it for team quests.\n`/drop <id>` - **Unregister** from a quest (before start).\n`/submit <id> [file|link|text]` - **Submit** proof for a taken quest (resubmit until the deadline).\n`/checkin <code>` - **Check in** to a live quest with the organizer's code.\n`/submissions <id>` - View your **submission history** for a quest.",
                 false,
             )
             .field(
                 "📊 Information & Utilities (Guild Members)",
-                "`/list` - Show the **quest board** in a paginated view.\n`/stats` - Get a DM with your **personal quest statistics** and active quests.\n`/view` - **View** quest details\n`/help` - Display this **help page**.",
+                "`/list [filters] [sort] [season]` - Show the **quest board** in a paginated view.\n`/search <text>` - **Search** quests by title and description.\n`/stats [season]` - Get a DM with your **personal quest statistics** and active quests.\n`/history [user] [season]` - Every quest you took with dates, **points** and proof.\n`/view` - **View** quest details\n`/notifications` - Choose which **notifications** you get, how, and your quiet hours.\n`/help` - Display this **help page**.",
+                false,
+            )
+            .field(
+                "🧾 Verification (Verifiers or Admins)",
+                "`/review queue [division] [community]` - Work through **pending submissions** with Approve / Reject / Skip.",
                 false,
             )
             .field(
                 "👑 Admin Command (Admins Only)",
-                "`/register_community <name> [leader]` - **Register a new community**.",
+                "`/register_community <name> [leader]` - **Register a new community**.\n`/quest import <file>` - **Bulk create** quests from a CSV or JSON file.\n`/export [from] [to] [division] [season]` - Download all data as a **zip** of CSV and JSON.\n`/season start|close|list` - Manage **event seasons**; boards and stats show the active one.",
                 false,
             )
             .color(0x3498DB) // A suitable blue color for info/help
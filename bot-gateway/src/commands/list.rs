@@ -1,35 +1,108 @@
-use crate::cache::get_cached_sheet_data;
+use crate::cache::{get_cached_sheet_data, get_guild_config, quest_is_published};
+use crate::commands::season::{resolve_season, scope_to_season, season_label};
+use crate::models::{Division, QuestCategory, QuestSort, StatusFilter};
 use crate::{Data, Error};
-use common::{calculate_status, QuestStatus};
+use common::{calculate_status, has_free_slot, normalize_name, slots_from_cell, slots_label, QuestStatus};
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use poise::serenity_prelude as serenity;
 use serenity::collector::ComponentInteractionCollector;
 use serenity::all::{
-    ButtonStyle, CreateActionRow, CreateButton, CreateInteractionResponse,
-    CreateInteractionResponseMessage, EditMessage,
+    ButtonStyle, ComponentInteractionDataKind, CreateActionRow, CreateButton, CreateInteractionResponse,
+    CreateInteractionResponseFollowup, CreateInteractionResponseMessage, CreateSelectMenu,
+    CreateSelectMenuKind, CreateSelectMenuOption, EditMessage,
 };
 use futures_util::stream::StreamExt;
+use std::time::Duration;
 
 type Context<'a> = poise::Context<'a, Data, Error>;
 
-async fn paginate_embeds(ctx: Context<'_>, embeds: Vec<serenity::CreateEmbed>) -> Result<(), Error> {
+/// One row of the quest board: a quest joined with its participant count.
+#[derive(Debug, Clone)]
+pub struct QuestSummary {
+    pub quest_id: String,
+    pub title: String,
+    pub description: String,
+    pub category: String,
+    pub organizer: String,
+    pub platform: String,
+    pub schedule_ts: i64,
+    pub deadline_ts: i64,
+    pub created_ts: i64,
+    /// `None` for quests without a slot limit.
+    pub max_slots: Option<u32>,
+    pub filled: u32,
+}
+
+fn parse_ts(iso: &str) -> i64 {
+    // 0 implies the time was not provided
+    DateTime::parse_from_rfc3339(iso).map(|dt| dt.timestamp()).unwrap_or(0)
+}
+
+/// Builds the quest board from raw Quests / Participants rows, in sheet order.
+/// Unpublished quests (drafts) are left out.
+/// Shared by `/list` and the scheduled digests.
+pub fn collect_quest_summaries(q_rows: &[Vec<String>], p_rows: &[Vec<String>]) -> Vec<QuestSummary> {
+    let mut participant_counts: HashMap<String, u32> = HashMap::new();
+
+    for row in p_rows.iter().skip(1) {
+        if row.len() >= 4 {
+            let q_id = row[0].clone();
+            let status = row[3].clone().to_uppercase();
+
+            if !q_id.is_empty() && (status == "ON_PROGRESS" || status == "CHECKED_IN" || status == "COMPLETED" || status == "VERIFIED") {
+                *participant_counts.entry(q_id.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut quests = Vec::new();
+
+    for row in q_rows.iter().skip(1) {
+        if row.len() >= 9 {
+            let q_id = row[0].clone();
+            if q_id == "Quest ID" || q_id.is_empty() || !quest_is_published(row) { continue; }
+
+            let cell = |idx: usize, default: &str| row.get(idx).map(|s| s.as_str()).unwrap_or(default).to_string();
+
+            quests.push(QuestSummary {
+                filled: *participant_counts.get(&q_id).unwrap_or(&0),
+                quest_id: q_id,
+                title: cell(1, "No Title"),
+                description: cell(7, ""),
+                category: cell(2, ""),
+                organizer: cell(4, "-"),
+                platform: cell(6, "-"),
+                schedule_ts: parse_ts(&row[5]),
+                deadline_ts: parse_ts(&row[8]),
+                created_ts: parse_ts(&cell(9, "")),
+                max_slots: slots_from_cell(&row[3]),
+            });
+        }
+    }
+
+    quests
+}
+
+/// Plain Prev/Next pager for a list of pages (e.g. `/history`), usable only by the invoking user.
+pub async fn paginate_embeds(ctx: Context<'_>, embeds: Vec<serenity::CreateEmbed>, ephemeral: bool) -> Result<(), Error> {
     if embeds.is_empty() {
         return Ok(());
     }
-    
-    let buttons = vec![
-        CreateButton::new("prev")
+
+    let prefix = format!("{}_page_", ctx.id());
+    let action_row = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}prev", prefix))
             .label("Prev")
             .style(ButtonStyle::Secondary),
-        CreateButton::new("next")
+        CreateButton::new(format!("{}next", prefix))
             .label("Next")
             .style(ButtonStyle::Primary),
-    ];
-    let action_row = CreateActionRow::Buttons(buttons);
+    ]);
 
     let mut reply = poise::CreateReply::default()
-        .embed(embeds[0].clone());
+        .embed(embeds[0].clone())
+        .ephemeral(ephemeral);
 
     if embeds.len() > 1 {
         reply = reply.components(vec![action_row.clone()]);
@@ -40,11 +113,128 @@ async fn paginate_embeds(ctx: Context<'_>, embeds: Vec<serenity::CreateEmbed>) -
     if embeds.len() <= 1 {
         return Ok(());
     }
-    
+
+    let mut page_idx: usize = 0;
+    let ctx_serenity = ctx.serenity_context();
+    let filter_prefix = prefix.clone();
+    let mut collector = ComponentInteractionCollector::new(ctx_serenity)
+        .author_id(ctx.author().id)
+        .filter(move |i| i.data.custom_id.starts_with(&filter_prefix))
+        .stream();
+
+    while let Ok(Some(interaction)) = tokio::time::timeout(BOARD_IDLE_TIMEOUT, collector.next()).await {
+        match interaction.data.custom_id.trim_start_matches(&prefix) {
+            "next" => page_idx = (page_idx + 1) % embeds.len(),
+            "prev" => page_idx = if page_idx == 0 { embeds.len() - 1 } else { page_idx - 1 },
+            _ => {}
+        }
+
+        let response = CreateInteractionResponse::UpdateMessage(CreateInteractionResponseMessage::new()
+            .embed(embeds[page_idx].clone())
+            .components(vec![action_row.clone()]));
+
+        if let Err(e) = interaction.create_response(&ctx_serenity.http, response).await {
+            eprintln!("Failed to update message: {:?}", e);
+        }
+    }
+
+    let _ = handle.edit(ctx, poise::CreateReply::default()
+        .embed(embeds[page_idx].clone())
+        .components(Vec::new())
+    ).await;
+
+    Ok(())
+}
+
+/// The board stays interactive until it has been idle this long.
+const BOARD_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+const STATUS_CHOICES: [(&str, &str, QuestStatus); 4] = [
+    ("upcoming", "🟢 Upcoming", QuestStatus::Upcoming),
+    ("ongoing", "🏃 Ongoing", QuestStatus::Ongoing),
+    ("ended", "🏁 Ended", QuestStatus::Ended),
+    ("tba", "⚪ TBA", QuestStatus::Tba),
+];
+
+const CATEGORY_CHOICES: [(&str, &str); 2] = [("CreativeArts", "🎨 Creative Arts"), ("Community", "🏘️ Community")];
+
+fn select_option(value: &str, label: &str, selected: bool) -> CreateSelectMenuOption {
+    CreateSelectMenuOption::new(label, value).default_selection(selected)
+}
+
+/// Buttons and select menus under the board. The Take menu lists the quests on
+/// the current page that can still be joined.
+fn board_components(prefix: &str, filter: &QuestFilter, page: &[QuestSummary], now: i64) -> Vec<CreateActionRow> {
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}first", prefix)).label("⏮ First").style(ButtonStyle::Secondary),
+        CreateButton::new(format!("{}prev", prefix)).label("Prev").style(ButtonStyle::Secondary),
+        CreateButton::new(format!("{}next", prefix)).label("Next").style(ButtonStyle::Primary),
+        CreateButton::new(format!("{}last", prefix)).label("Last ⏭").style(ButtonStyle::Secondary),
+    ]);
+
+    let mut categories = vec![select_option("all", "📁 All categories", filter.category.is_none())];
+    categories.extend(CATEGORY_CHOICES.iter()
+        .map(|(value, label)| select_option(value, label, filter.category.as_deref() == Some(*value))));
+
+    let mut statuses = vec![select_option("all", "📋 Any status", filter.status.is_none())];
+    statuses.extend(STATUS_CHOICES.iter()
+        .map(|(value, label, status)| select_option(value, label, filter.status == Some(*status))));
+
+    let mut rows = vec![
+        buttons,
+        CreateActionRow::SelectMenu(CreateSelectMenu::new(format!("{}category", prefix), CreateSelectMenuKind::String { options: categories })),
+        CreateActionRow::SelectMenu(CreateSelectMenu::new(format!("{}status", prefix), CreateSelectMenuKind::String { options: statuses })),
+    ];
+
+    let takeable: Vec<CreateSelectMenuOption> = page.iter()
+        .filter(|q| calculate_status(now, &q.schedule_ts, &q.deadline_ts) != QuestStatus::Ended && has_free_slot(q.filled, q.max_slots))
+        .map(|q| {
+            let label: String = q.title.chars().take(100).collect();
+            CreateSelectMenuOption::new(label, &q.quest_id).description(format!("{}/{} slots", q.filled, slots_label(q.max_slots)))
+        })
+        .collect();
+    if !takeable.is_empty() {
+        rows.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(format!("{}take", prefix), CreateSelectMenuKind::String { options: takeable })
+                .placeholder("⚔️ Take a quest from this page")
+        ));
+    }
+    rows
+}
+
+/// Posts the quest board and keeps it interactive: paging, category and status
+/// select menus that re-render in place, and a Take menu. Only the invoking user
+/// can use the controls.
+async fn quest_board(ctx: Context<'_>, mut quests: Vec<QuestSummary>, mut filter: QuestFilter, heading: &str) -> Result<(), Error> {
+    let now = Utc::now().timestamp();
+    let items_per_page = 5;
+    let mut page_idx: usize = 0;
+
+    let render = |quests: &[QuestSummary], filter: &QuestFilter, page_idx: usize| {
+        let visible: Vec<QuestSummary> = quests.iter().filter(|q| filter.matches(q, now)).cloned().collect();
+        let mut embeds = board_embeds(&visible, heading, now);
+        if embeds.is_empty() {
+            embeds.push(serenity::CreateEmbed::new()
+                .title(heading)
+                .description("📭 No quests match these filters.")
+                .color(0x3498DB));
+        }
+        let page_idx = page_idx.min(embeds.len() - 1);
+        let page: Vec<QuestSummary> = visible.chunks(items_per_page).nth(page_idx).map(|c| c.to_vec()).unwrap_or_default();
+        (embeds, page_idx, page)
+    };
+
+    let prefix = format!("{}_board_", ctx.id());
+    let (embeds, _, page) = render(&quests, &filter, page_idx);
+    let mut page_count = embeds.len();
+
+    let handle = ctx.send(poise::CreateReply::default()
+        .embed(embeds[0].clone())
+        .components(board_components(&prefix, &filter, &page, now))
+    ).await?;
     let message = handle.message().await?;
 
     let author_id = ctx.author().id;
-    let mut page_idx: usize = 0;
     let msg_id = message.id;
     let channel_id = message.channel_id;
 
@@ -52,160 +242,317 @@ async fn paginate_embeds(ctx: Context<'_>, embeds: Vec<serenity::CreateEmbed>) -
     let mut collector = ComponentInteractionCollector::new(ctx_serenity)
         .message_id(msg_id)
         .channel_id(channel_id)
-        .author_id(author_id)
-        .timeout(std::time::Duration::from_secs(120))
         .stream();
 
-    while let Some(interaction) = collector.next().await {
-        let custom_id = &interaction.data.custom_id;
-
-        match custom_id.as_str() {
-            "next" => page_idx = (page_idx + 1) % embeds.len(),
-            "prev" => page_idx = if page_idx == 0 { embeds.len() - 1 } else { page_idx - 1 },
-            _ => {}
+    while let Ok(Some(interaction)) = tokio::time::timeout(BOARD_IDLE_TIMEOUT, collector.next()).await {
+        if interaction.user.id != author_id {
+            let response = CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                .content("🚫 Only the member who opened this board can use it. Run `/list` for your own.")
+                .ephemeral(true));
+            let _ = interaction.create_response(&ctx_serenity.http, response).await;
+            continue;
         }
 
-        let response_data = CreateInteractionResponseMessage::new()
-            .embed(embeds[page_idx].clone())
-            .components(vec![action_row.clone()]);
-
-        let response = CreateInteractionResponse::UpdateMessage(response_data);
+        let action = interaction.data.custom_id.trim_start_matches(&prefix).to_string();
+        let selected = match &interaction.data.kind {
+            ComponentInteractionDataKind::StringSelect { values } => values.first().cloned().unwrap_or_default(),
+            _ => String::new(),
+        };
+
+        match action.as_str() {
+            "first" => page_idx = 0,
+            "prev" => page_idx = if page_idx == 0 { page_count - 1 } else { page_idx - 1 },
+            "next" => page_idx = (page_idx + 1) % page_count,
+            "last" => page_idx = page_count - 1,
+            "category" => {
+                filter.category = CATEGORY_CHOICES.iter().find(|(value, _)| *value == selected).map(|(value, _)| value.to_string());
+                page_idx = 0;
+            }
+            "status" => {
+                filter.status = STATUS_CHOICES.iter().find(|(value, _, _)| *value == selected).map(|(_, _, status)| *status);
+                page_idx = 0;
+            }
+            "take" => {
+                interaction.defer_ephemeral(&ctx_serenity.http).await?;
+                let reply = if crate::security::is_participant(ctx).await? {
+                    crate::commands::quest::take_quest(ctx, &selected).await?
+                } else {
+                    "⛔ Access Denied: Only CaStaff can take quests.".to_string()
+                };
+                // The sheet catches up later, so count the new participant here.
+                if reply.starts_with('✅') {
+                    if let Some(quest) = quests.iter_mut().find(|q| q.quest_id == selected) {
+                        quest.filled += 1;
+                    }
+                }
+                interaction.create_followup(&ctx_serenity.http, CreateInteractionResponseFollowup::new()
+                    .content(reply)
+                    .ephemeral(true)
+                ).await?;
+            }
+            _ => continue,
+        }
 
-        if let Err(e) = interaction.create_response(&ctx_serenity.http, response).await {
+        let (embeds, clamped, page) = render(&quests, &filter, page_idx);
+        page_idx = clamped;
+        page_count = embeds.len();
+
+        let components = board_components(&prefix, &filter, &page, now);
+        let updated = if action == "take" {
+            // Already acknowledged with the ephemeral reply, so edit the board directly.
+            let edit = EditMessage::new().embed(embeds[page_idx].clone()).components(components);
+            channel_id.edit_message(&ctx_serenity.http, msg_id, edit).await.map(|_| ())
+        } else {
+            let response_data = CreateInteractionResponseMessage::new()
+                .embed(embeds[page_idx].clone())
+                .components(components);
+            interaction.create_response(&ctx_serenity.http, CreateInteractionResponse::UpdateMessage(response_data)).await
+        };
+
+        if let Err(e) = updated {
             eprintln!("Failed to update message: {:?}", e);
         }
     }
 
+    let (embeds, _, _) = render(&quests, &filter, page_idx);
     let edit_final = EditMessage::new()
-        .embed(embeds[page_idx].clone())
+        .embed(embeds[page_idx.min(embeds.len() - 1)].clone())
         .components(Vec::new());
     let _ = channel_id.edit_message(&ctx_serenity.http, msg_id, edit_final).await;
 
     Ok(())
 }
 
+/// `/list` options. Every field left `None`/`false` keeps all quests.
+#[derive(Debug, Default)]
+pub struct QuestFilter {
+    pub status: Option<QuestStatus>,
+    pub category: Option<String>,
+    /// Organizer column: a division name or a community name.
+    pub organizer: Option<String>,
+    pub open_slots: bool,
+}
+
+impl QuestFilter {
+    /// Whether any option narrows the list.
+    pub fn is_set(&self) -> bool {
+        self.status.is_some() || self.category.is_some() || self.organizer.is_some() || self.open_slots
+    }
+
+    pub fn matches(&self, quest: &QuestSummary, now: i64) -> bool {
+        let status = calculate_status(now, &quest.schedule_ts, &quest.deadline_ts);
+        self.status.is_none_or(|s| s == status)
+            && self.category.as_ref().is_none_or(|c| quest.category.eq_ignore_ascii_case(c))
+            && self.organizer.as_ref().is_none_or(|o| normalize_name(&quest.organizer) == normalize_name(o))
+            && (!self.open_slots || (status != QuestStatus::Ended && has_free_slot(quest.filled, quest.max_slots)))
+    }
+}
+
+/// Sorts by start time or deadline, soonest first; quests without a date go last.
+pub fn sort_quests(quests: &mut [QuestSummary], sort: QuestSort) {
+    let key = |q: &QuestSummary| {
+        let ts = match sort {
+            QuestSort::Start => q.schedule_ts,
+            QuestSort::Deadline => q.deadline_ts,
+        };
+        if ts == 0 { i64::MAX } else { ts }
+    };
+    quests.sort_by_key(key);
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+fn words(s: &str) -> Vec<String> {
+    normalize_name(s)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// Fuzzy match of a search query against some text, both normalized. Every query
+/// word must appear in the text, as part of a word or with a small typo.
+/// Returns a score (lower is better), or `None` if it doesn't match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text_words = words(text);
+    let query_words = words(query);
+    if query_words.is_empty() {
+        return None;
+    }
+
+    let mut score = 0;
+    for q in &query_words {
+        let allowed = match q.chars().count() {
+            0..=3 => 0,
+            4..=7 => 1,
+            _ => 2,
+        };
+        let best = text_words.iter()
+            .map(|w| if w.contains(q.as_str()) { 0 } else { edit_distance(q, w) })
+            .min()?;
+        if best > allowed {
+            return None;
+        }
+        score += best;
+    }
+    Some(score)
+}
+
+/// Ranks quests for `/search`: title matches first, then description matches.
+pub fn search_quests(quests: Vec<QuestSummary>, query: &str) -> Vec<QuestSummary> {
+    let mut ranked: Vec<(usize, QuestSummary)> = quests.into_iter()
+        .filter_map(|q| {
+            let score = fuzzy_score(query, &q.title)
+                .or_else(|| fuzzy_score(query, &format!("{} {}", q.title, q.description)).map(|s| s + 10))?;
+            Some((score, q))
+        })
+        .collect();
+    ranked.sort_by_key(|(score, _)| *score);
+    ranked.into_iter().map(|(_, q)| q).collect()
+}
+
+/// Renders quests as quest board pages, five per page.
+fn board_embeds(quests: &[QuestSummary], heading: &str, now: i64) -> Vec<serenity::CreateEmbed> {
+    let items_per_page = 5;
+    let chunks: Vec<_> = quests.chunks(items_per_page).collect();
+
+    let mut embeds: Vec<serenity::CreateEmbed> = Vec::new();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let mut embed = serenity::CreateEmbed::new()
+            .title(format!("{} — Page {} of {}", heading, i + 1, chunks.len()))
+            .color(0x3498DB);
+
+        for quest in *chunk {
+            let QuestSummary { quest_id: q_id, title, organizer, schedule_ts, deadline_ts, max_slots, filled, .. } = quest;
+
+            let status = calculate_status(now, schedule_ts, deadline_ts);
+            
+            let title_display = if status == QuestStatus::Ended {format!("~~{}~~ (Ended)", title)} else {title.clone()};
+
+            let (status_icon, time_msg, is_active) = match status {
+                QuestStatus::Ended => (
+                    "🏁", 
+                    format!("Ended <t:{}:R>", deadline_ts), 
+                    false
+                ),
+                QuestStatus::Ongoing => (
+                    "🏃", 
+                    format!("**HAPPENING NOW!**\nEnds <t:{}:R>", deadline_ts), 
+                    true
+                ),
+                QuestStatus::Upcoming => (
+                    "🟢", 
+                    format!("<t:{}:f> (<t:{}:R>)", schedule_ts, schedule_ts), 
+                    true
+                ),
+                QuestStatus::Tba => ("⚪", "Date TBA".to_string(), true),
+            };
+            // --- SLOT LOGIC ---
+            // Only show slot status if the quest hasn't ended
+            let slot_str = if !is_active {
+                "❌ **Closed**".to_string()
+            } else if !has_free_slot(*filled, *max_slots) {
+                format!("🔴 **FULL** ({}/{})", filled, slots_label(*max_slots))
+            } else {
+                format!("{} **Open** ({}/{})", status_icon, filled, slots_label(*max_slots))
+            };
+
+            // Construct Field
+            let field_name = format!("{} — {}", title_display, q_id);
+            let field_value = format!(
+                "• Status: {}\n• By: {}\n• Time: {}\n", 
+                slot_str, organizer, time_msg
+            );
+
+            embed = embed.field(field_name, field_value, false);
+        }
+
+        embeds.push(embed);
+    }
+    embeds
+}
+
+// Every slash option is a parameter, so the argument count grows with the options.
+#[allow(clippy::too_many_arguments)]
 #[poise::command(slash_command, description_localized("en-US", "List all available quests"), check = "crate::security::check_guild")]
-pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "Only quests with this status"] status: Option<StatusFilter>,
+    #[description = "Only quests in this category"] category: Option<QuestCategory>,
+    #[description = "Only quests from this division"] division: Option<Division>,
+    #[description = "Only quests from this community"] community: Option<String>,
+    #[description = "Only quests that still have open slots"] open_slots: Option<bool>,
+    #[description = "Sort by start time or deadline (default: sheet order)"] sort: Option<QuestSort>,
+    #[description = "Season ID, or \"all\" (default: the active season)"]
+    #[autocomplete = "crate::commands::season::autocomplete_season"]
+    season: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
     let now = Utc::now().timestamp();
 
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    let season_given = season.is_some();
+    let season = match resolve_season(&config, season.as_deref()) {
+        Ok(season) => season,
+        Err(msg) => {
+            ctx.say(format!("❌ {}", msg)).await?;
+            return Ok(());
+        }
+    };
+
     let result = get_cached_sheet_data(ctx).await;
 
     match result {
         Ok(data) => {
-
-            let mut participant_counts: HashMap<String, i8> = HashMap::new();
-            
-            for row in data.p_rows.iter().skip(1) {
-                if row.len() >= 4 {
-                    let q_id = row[0].clone();
-                    let status = row[3].clone().to_uppercase();
-                    
-                    if !q_id.is_empty() && (status == "ON_PROGRESS" || status == "COMPLETED" || status == "VERIFIED") {
-                        *participant_counts.entry(q_id.to_string()).or_insert(0) += 1;
-                    }
-                }
+            let filter = QuestFilter {
+                status: status.map(|s| match s {
+                    StatusFilter::Upcoming => QuestStatus::Upcoming,
+                    StatusFilter::Ongoing => QuestStatus::Ongoing,
+                    StatusFilter::Ended => QuestStatus::Ended,
+                    StatusFilter::Tba => QuestStatus::Tba,
+                }),
+                category: category.map(|c| format!("{:?}", c)),
+                organizer: match (division, community) {
+                    (Some(division), _) if !matches!(division, Division::None) => Some(format!("{:?}", division)),
+                    (_, Some(community)) if !community.trim().is_empty() => Some(community),
+                    _ => None,
+                },
+                open_slots: open_slots.unwrap_or(false),
+            };
+
+            let (q_rows, p_rows) = scope_to_season(&data.q_rows, &data.p_rows, season.as_ref());
+            let mut display_quests = collect_quest_summaries(&q_rows, &p_rows);
+            if let Some(sort) = sort {
+                sort_quests(&mut display_quests, sort);
             }
 
-            let mut display_quests: Vec<(String, String, String, i64, i64, i8, i8)> = Vec::new();
-
-            for row in data.q_rows.iter().skip(1) {
-                if row.len() >= 9 {
-                    let q_id = row[0].clone();
-                    let title = row.get(1).map(|s| s.as_str()).unwrap_or("No Title").to_string();
-                    let organizer = row.get(4).map(|s| s.as_str()).unwrap_or("-").to_string();
-                    let schedule_str = row[5].clone();
-                
-                    let deadline_str = row[8].clone(); 
-                    
-                    let max_slots = row[3].parse::<i8>().unwrap_or(0);
-
-                    if q_id == "Quest ID" || q_id.is_empty() { continue; }
-
-                    let current_filled = *participant_counts.get(&q_id).unwrap_or(&0);
-
-                    // Parse Start Time
-                    let schedule_ts = if let Ok(dt) = DateTime::parse_from_rfc3339(&schedule_str) {
-                        dt.timestamp()
-                    } else {
-                        0
-                    };
-
-                    // Parse Deadline/End Time
-                    let deadline_ts = if let Ok(dt) = DateTime::parse_from_rfc3339(&deadline_str) {
-                        dt.timestamp()
-                    } else {
-                        0 // 0 implies no deadline provided
-                    };
-
-                    display_quests.push((q_id, title, organizer, schedule_ts, deadline_ts, max_slots, current_filled));
-                }
-            }
-
-            if display_quests.is_empty() {
-                ctx.say("📭 There're no active quest at the moment.").await?;
+            if !display_quests.iter().any(|q| filter.matches(q, now)) {
+                let message = if filter.is_set() || (season_given && season.is_some()) {
+                    "📭 No quests match these filters."
+                } else {
+                    "📭 There're no active quest at the moment."
+                };
+                ctx.say(message).await?;
                 return Ok(());
             }
-            
-            let items_per_page = 5;
-            let chunks: Vec<_> = display_quests.chunks(items_per_page).collect();
-
-            let mut embeds: Vec<serenity::CreateEmbed> = Vec::new();
-
-            for (i, chunk) in chunks.iter().enumerate() {
-                let mut embed = serenity::CreateEmbed::new()
-                    .title(format!("📜 Quest Board — Page {} of {}", i + 1, chunks.len()))
-                    .color(0x3498DB);
-
-                for (q_id, title, organizer, schedule_ts, deadline_ts, max_slots, filled) in *chunk {
-                    
-                    let status = calculate_status(now, schedule_ts, deadline_ts);
-                    
-                    let title_display = if status == QuestStatus::Ended {format!("~~{}~~ (Ended)", title)} else {title.clone()};
-
-                    let (status_icon, time_msg, is_active) = match status {
-                        QuestStatus::Ended => (
-                            "🏁", 
-                            format!("Ended <t:{}:R>", deadline_ts), 
-                            false
-                        ),
-                        QuestStatus::Ongoing => (
-                            "🏃", 
-                            format!("**HAPPENING NOW!**\nEnds <t:{}:R>", deadline_ts), 
-                            true
-                        ),
-                        QuestStatus::Upcoming => (
-                            "🟢", 
-                            format!("<t:{}:f> (<t:{}:R>)", schedule_ts, schedule_ts), 
-                            true
-                        ),
-                        QuestStatus::Tba => ("⚪", "Date TBA".to_string(), true),
-                    };
-                    // --- SLOT LOGIC ---
-                    // Only show slot status if the quest hasn't ended
-                    let slot_str = if !is_active {
-                        "❌ **Closed**".to_string()
-                    } else if *filled >= *max_slots {
-                        format!("🔴 **FULL** ({}/{})", filled, max_slots)
-                    } else {
-                        format!("{} **Open** ({}/{})", status_icon, filled, max_slots)
-                    };
-
-                    // Construct Field
-                    let field_name = format!("{} — {}", title_display, q_id);
-                    let field_value = format!(
-                        "• Status: {}\n• By: {}\n• Time: {}\n", 
-                        slot_str, organizer, time_msg
-                    );
-
-                    embed = embed.field(field_name, field_value, false);
-                }
 
-                embeds.push(embed);
-            }
-
-            paginate_embeds(ctx, embeds).await?;
+            let heading = format!("📜 Quest Board{}", season_label(season.as_ref()));
+            quest_board(ctx, display_quests, filter, &heading).await?;
         },
         Err(e) => {
             eprintln!("Sheet Error: {:?}", e);
@@ -216,6 +563,25 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+#[poise::command(slash_command, description_localized("en-US", "Search quests by title and description"), check = "crate::security::check_guild")]
+pub async fn search(
+    ctx: Context<'_>,
+    #[description = "Words to look for (small typos are fine)"] text: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let results = search_quests(collect_quest_summaries(&data.q_rows, &data.p_rows), &text);
+
+    if results.is_empty() {
+        ctx.say(format!("🔍 No quests match \"{}\".", text)).await?;
+        return Ok(());
+    }
+
+    quest_board(ctx, results, QuestFilter::default(), &format!("🔍 Results for \"{}\"", text)).await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     // Import everything from the parent module (so we can see calculate_status)
@@ -271,4 +637,80 @@ mod tests {
         let result = calculate_status(now, &start, &end);
         assert_eq!(result, QuestStatus::Ongoing);
     }
-}
\ No newline at end of file
+
+    fn summary(id: &str, title: &str, description: &str, start: i64, end: i64, filled: u32, max: u32) -> QuestSummary {
+        QuestSummary {
+            quest_id: id.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            category: "CreativeArts".to_string(),
+            organizer: "Illust".to_string(),
+            platform: "Discord".to_string(),
+            schedule_ts: start,
+            deadline_ts: end,
+            created_ts: 0,
+            max_slots: Some(max),
+            filled,
+        }
+    }
+
+    #[test]
+    fn test_quest_filter() {
+        let now = 2500;
+        let open = summary("q1", "Open", "", 2000, 3000, 1, 5);
+        let full = summary("q2", "Full", "", 2000, 3000, 5, 5);
+        let ended = summary("q3", "Ended", "", 1000, 2000, 1, 5);
+        let unlimited = QuestSummary { max_slots: None, ..summary("q4", "Workshop", "", 2000, 3000, 250, 0) };
+
+        let open_only = QuestFilter { open_slots: true, ..Default::default() };
+        assert!(open_only.matches(&open, now));
+        assert!(!open_only.matches(&full, now));
+        assert!(!open_only.matches(&ended, now));
+        assert!(open_only.matches(&unlimited, now));
+
+        let ongoing = QuestFilter { status: Some(QuestStatus::Ongoing), ..Default::default() };
+        assert!(ongoing.matches(&full, now));
+        assert!(!ongoing.matches(&ended, now));
+
+        let by_org = QuestFilter { organizer: Some("illust".to_string()), category: Some("creativearts".to_string()), ..Default::default() };
+        assert!(by_org.matches(&open, now));
+        let other = QuestFilter { organizer: Some("Music".to_string()), ..Default::default() };
+        assert!(!other.matches(&open, now));
+
+        assert!(!QuestFilter::default().is_set());
+        assert!(open_only.is_set() && ongoing.is_set() && other.is_set());
+    }
+
+    #[test]
+    fn test_sort_quests_puts_tba_last() {
+        let mut quests = vec![
+            summary("tba", "", "", 0, 0, 0, 1),
+            summary("late", "", "", 3000, 4000, 0, 1),
+            summary("early", "", "", 1000, 5000, 0, 1),
+        ];
+        sort_quests(&mut quests, QuestSort::Start);
+        let ids: Vec<&str> = quests.iter().map(|q| q.quest_id.as_str()).collect();
+        assert_eq!(ids, vec!["early", "late", "tba"]);
+
+        sort_quests(&mut quests, QuestSort::Deadline);
+        let ids: Vec<&str> = quests.iter().map(|q| q.quest_id.as_str()).collect();
+        assert_eq!(ids, vec!["late", "early", "tba"]);
+    }
+
+    #[test]
+    fn test_fuzzy_search() {
+        assert_eq!(fuzzy_score("MLBB", "5v5 mlbb Fun Match"), Some(0));
+        assert_eq!(fuzzy_score("turnament", "Tournament Night"), Some(1));
+        assert_eq!(fuzzy_score("ＦＵＮ", "Fun Match"), Some(0));
+        assert_eq!(fuzzy_score("cosplay", "Fun Match"), None);
+        assert_eq!(fuzzy_score("", "Fun Match"), None);
+
+        let quests = vec![
+            summary("q1", "Drawing jam", "Draw a cat", 0, 0, 0, 1),
+            summary("q2", "Cat drawing", "", 0, 0, 0, 1),
+            summary("q3", "Music night", "", 0, 0, 0, 1),
+        ];
+        let ids: Vec<String> = search_quests(quests, "cat").into_iter().map(|q| q.quest_id).collect();
+        assert_eq!(ids, vec!["q2", "q1"]);
+    }
+}
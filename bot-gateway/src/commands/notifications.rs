@@ -0,0 +1,130 @@
+use poise::{ChoiceParameter, CreateReply};
+
+use crate::{Data, Error, cache::{get_notification_prefs, set_notification_prefs}, models::{NotificationCategory, NotificationDelivery}};
+
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+#[poise::command(
+    slash_command,
+    subcommands("toggle", "delivery", "quiet_hours", "clear_quiet_hours", "view"),
+    description_localized("en-US", "Manage your notification preferences"),
+    check = "crate::security::check_guild"
+)]
+pub async fn notifications(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Turn a notification category on or off"))]
+pub async fn toggle(
+    ctx: Context<'_>,
+    #[description = "Notification category"] category: NotificationCategory,
+    #[description = "Receive these notifications?"] enabled: bool,
+) -> Result<(), Error> {
+    let user_id = ctx.author().id.to_string();
+    let redis_client = &ctx.data().redis_client;
+
+    let mut prefs = get_notification_prefs(redis_client, &user_id).await.unwrap_or_default();
+    prefs.set_enabled(category, enabled);
+    set_notification_prefs(redis_client, &user_id, &prefs).await?;
+
+    ctx.send(CreateReply::default()
+        .content(format!("✅ {} notifications {}.", category.name(), if enabled { "enabled" } else { "disabled" }))
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Choose how notifications reach you"))]
+pub async fn delivery(
+    ctx: Context<'_>,
+    #[description = "Delivery method"] method: NotificationDelivery,
+) -> Result<(), Error> {
+    let user_id = ctx.author().id.to_string();
+    let redis_client = &ctx.data().redis_client;
+
+    let mut prefs = get_notification_prefs(redis_client, &user_id).await.unwrap_or_default();
+    prefs.delivery = method;
+    set_notification_prefs(redis_client, &user_id, &prefs).await?;
+
+    ctx.send(CreateReply::default()
+        .content(format!("✅ Notifications will be delivered by: {}", method.name()))
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Hold notifications during these hours (WIB)"))]
+pub async fn quiet_hours(
+    ctx: Context<'_>,
+    #[description = "Start hour in WIB (0-23)"]
+    #[min = 0]
+    #[max = 23]
+    start: u8,
+    #[description = "End hour in WIB (0-23, exclusive)"]
+    #[min = 0]
+    #[max = 23]
+    end: u8,
+) -> Result<(), Error> {
+    if start == end {
+        ctx.send(CreateReply::default()
+            .content("❌ Start and end hour must be different.")
+            .ephemeral(true)
+        ).await?;
+        return Ok(());
+    }
+
+    let user_id = ctx.author().id.to_string();
+    let redis_client = &ctx.data().redis_client;
+
+    let mut prefs = get_notification_prefs(redis_client, &user_id).await.unwrap_or_default();
+    prefs.quiet_hours = Some((start, end));
+    set_notification_prefs(redis_client, &user_id, &prefs).await?;
+
+    ctx.send(CreateReply::default()
+        .content(format!("✅ Quiet hours set to {:02}:00 - {:02}:00 WIB. Notifications in this window are delivered afterwards.", start, end))
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Remove your quiet hours"))]
+pub async fn clear_quiet_hours(ctx: Context<'_>) -> Result<(), Error> {
+    let user_id = ctx.author().id.to_string();
+    let redis_client = &ctx.data().redis_client;
+
+    let mut prefs = get_notification_prefs(redis_client, &user_id).await.unwrap_or_default();
+    prefs.quiet_hours = None;
+    set_notification_prefs(redis_client, &user_id, &prefs).await?;
+
+    ctx.send(CreateReply::default()
+        .content("✅ Quiet hours cleared.")
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "View your notification preferences"))]
+pub async fn view(ctx: Context<'_>) -> Result<(), Error> {
+    let user_id = ctx.author().id.to_string();
+    let prefs = get_notification_prefs(&ctx.data().redis_client, &user_id).await.unwrap_or_default();
+
+    let fmt_toggle = |enabled: bool| if enabled { "✅ On" } else { "❌ Off" };
+
+    let mut content = String::from("**🔔 Notification Preferences**\n\n**Categories**\n");
+    for category in NotificationCategory::ALL {
+        content.push_str(&format!("{}: {}\n", category.name(), fmt_toggle(prefs.is_enabled(category))));
+    }
+    content.push_str(&format!(
+        "\n**Delivery**: {}\n**Quiet Hours**: {}",
+        prefs.delivery.name(),
+        prefs.quiet_hours
+            .map(|(start, end)| format!("{:02}:00 - {:02}:00 WIB", start, end))
+            .unwrap_or_else(|| "Not set".to_string()),
+    ));
+
+    ctx.send(CreateReply::default()
+        .content(content)
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
@@ -0,0 +1,330 @@
+use chrono::{DateTime, Utc};
+use common::{calculate_status, free_slots, slots_from_cell, slots_label, QuestStatus};
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use redis::AsyncCommands;
+use serenity::all::{CreateEmbed, CreateMessage};
+
+use crate::cache::{delete_party, get_cached_sheet_data, get_guild_config, get_party, get_user_party, quest_is_published, quest_prerequisites, quest_team_size, save_party};
+use crate::commands::quest::missing_prerequisites;
+use crate::kafka::produce_event;
+use crate::models::{NotificationCategory, Party, PartyMember, TeamRegistrationPayload};
+use crate::notify::{notify_users, send_dm};
+use crate::{Data, Error};
+
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+const MAX_PARTY_SIZE: usize = 25;
+const INVITE_TTL_SECS: u64 = 24 * 3600;
+/// How long `/party take` waits for the worker's verdict before answering.
+const CONFIRM_WAIT_SECS: u64 = 10;
+/// How long a background task keeps waiting after that, to DM the verdict instead.
+const CONFIRM_DM_WAIT_SECS: u64 = 300;
+
+/// Redis key where the sheet worker records whether a team registration went through:
+/// `OK`, or `REJECTED:<reason>`. Must match the worker's `TAKE_QUEST_TEAM` handler.
+fn team_result_key(quest_id: &str, team_id: &str) -> String {
+    format!("team_registration:{}:{}", quest_id, team_id)
+}
+
+/// Polls for the worker's verdict on a team registration for up to `secs` seconds.
+/// `Some(Ok)` when every member was registered, `Some(Err(reason))` when nobody was.
+async fn wait_for_team_result(redis_client: &redis::Client, key: &str, secs: u64) -> Result<Option<Result<(), String>>, Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    for _ in 0..secs {
+        let value: Option<String> = con.get(key).await?;
+        if let Some(value) = value {
+            return Ok(Some(match value.strip_prefix("REJECTED:") {
+                Some(reason) => Err(reason.trim().to_string()),
+                None => Ok(()),
+            }));
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+    Ok(None)
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("create", "invite", "join", "leave", "view", "take"),
+    description_localized("en-US", "Form a party to take team quests together"),
+    check = "crate::security::check_guild",
+    check = "crate::security::check_participant_role"
+)]
+pub async fn party(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn reply(ctx: Context<'_>, content: impl Into<String>) -> Result<(), Error> {
+    ctx.send(CreateReply::default().content(content).ephemeral(true)).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Start a new party with you as leader"))]
+pub async fn create(ctx: Context<'_>) -> Result<(), Error> {
+    let redis_client = &ctx.data().redis_client;
+    let user_id = ctx.author().id.to_string();
+
+    if get_user_party(redis_client, &user_id).await?.is_some() {
+        return reply(ctx, "❌ You're already in a party. Use `/party leave` first.").await;
+    }
+
+    let party = Party {
+        party_id: uuid::Uuid::new_v4().to_string(),
+        leader_id: user_id.clone(),
+        members: vec![PartyMember { user_id, user_tag: ctx.author().tag() }],
+    };
+    save_party(redis_client, &party).await?;
+
+    reply(ctx, "✅ Party created. Invite members with `/party invite @user`.").await
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Invite a member to your party"))]
+pub async fn invite(
+    ctx: Context<'_>,
+    #[description = "Member to invite"] user: serenity::User,
+) -> Result<(), Error> {
+    let redis_client = &ctx.data().redis_client;
+    let author_id = ctx.author().id.to_string();
+
+    let Some(party) = get_user_party(redis_client, &author_id).await? else {
+        return reply(ctx, "❌ You're not in a party. Use `/party create` first.").await;
+    };
+    if party.leader_id != author_id {
+        return reply(ctx, "❌ Only the party leader can invite members.").await;
+    }
+    if user.bot || user.id == ctx.author().id {
+        return reply(ctx, "❌ You can't invite that user.").await;
+    }
+    if party.members.len() >= MAX_PARTY_SIZE {
+        return reply(ctx, format!("❌ Parties are limited to {} members.", MAX_PARTY_SIZE)).await;
+    }
+    if get_user_party(redis_client, &user.id.to_string()).await?.is_some() {
+        return reply(ctx, format!("❌ {} is already in a party.", user.name)).await;
+    }
+
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let _: () = con.set_ex(format!("party_invite:{}", user.id), &party.party_id, INVITE_TTL_SECS).await?;
+
+    send_dm(
+        &ctx.serenity_context().http,
+        &user.id.to_string(),
+        CreateMessage::new().content(format!(
+            "🤝 **{}** invited you to their party. Run `/party join` in the server within 24 hours to accept.",
+            ctx.author().name
+        )),
+    ).await;
+
+    reply(ctx, format!("✅ Invited <@{}>. They can accept with `/party join`.", user.id)).await
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Accept your pending party invite"))]
+pub async fn join(ctx: Context<'_>) -> Result<(), Error> {
+    let redis_client = &ctx.data().redis_client;
+    let user_id = ctx.author().id.to_string();
+
+    if get_user_party(redis_client, &user_id).await?.is_some() {
+        return reply(ctx, "❌ You're already in a party. Use `/party leave` first.").await;
+    }
+
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let invite_key = format!("party_invite:{}", user_id);
+    let party_id: Option<String> = con.get(&invite_key).await?;
+    let _: () = con.del(&invite_key).await?;
+
+    let Some(party_id) = party_id else {
+        return reply(ctx, "❌ You have no pending party invite.").await;
+    };
+    let Some(mut party) = get_party(redis_client, &party_id).await? else {
+        return reply(ctx, "❌ That party no longer exists.").await;
+    };
+    if party.members.len() >= MAX_PARTY_SIZE {
+        return reply(ctx, "❌ That party is full.").await;
+    }
+
+    party.members.push(PartyMember { user_id, user_tag: ctx.author().tag() });
+    save_party(redis_client, &party).await?;
+
+    reply(ctx, format!("✅ Joined <@{}>'s party ({} members).", party.leader_id, party.members.len())).await
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Leave your party (the leader disbands it)"))]
+pub async fn leave(ctx: Context<'_>) -> Result<(), Error> {
+    let redis_client = &ctx.data().redis_client;
+    let user_id = ctx.author().id.to_string();
+
+    let Some(mut party) = get_user_party(redis_client, &user_id).await? else {
+        return reply(ctx, "❌ You're not in a party.").await;
+    };
+
+    if party.leader_id == user_id {
+        delete_party(redis_client, &party).await?;
+        return reply(ctx, "✅ Party disbanded.").await;
+    }
+
+    party.members.retain(|m| m.user_id != user_id);
+    save_party(redis_client, &party).await?;
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let _: () = con.del(format!("party_member:{}", user_id)).await?;
+
+    reply(ctx, "✅ You left the party.").await
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Show your party members"))]
+pub async fn view(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(party) = get_user_party(&ctx.data().redis_client, &ctx.author().id.to_string()).await? else {
+        return reply(ctx, "📭 You're not in a party. Use `/party create` to start one.").await;
+    };
+
+    let members = party.members.iter()
+        .map(|m| if m.user_id == party.leader_id { format!("• <@{}> 👑", m.user_id) } else { format!("• <@{}>", m.user_id) })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let embed = CreateEmbed::default()
+        .title(format!("🤝 Party ({} members)", party.members.len()))
+        .description(members)
+        .field("Team ID", &party.party_id, false)
+        .color(0x3498DB);
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true)).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Register your whole party for a team quest"))]
+pub async fn take(
+    ctx: Context<'_>,
+    #[description = "Team quest"]
+    #[autocomplete = "crate::commands::quest::autocomplete_quest_id"]
+    quest_id: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let user_id = ctx.author().id.to_string();
+
+    let Some(party) = get_user_party(&ctx.data().redis_client, &user_id).await? else {
+        ctx.say("❌ You're not in a party. Use `/party create` first.").await?;
+        return Ok(());
+    };
+    if party.leader_id != user_id {
+        ctx.say("❌ Only the party leader can register the party.").await?;
+        return Ok(());
+    }
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let Some(row) = data.q_rows.iter().skip(1).find(|row| row.len() >= 9 && row[0] == quest_id && quest_is_published(row)) else {
+        ctx.say(format!("❌ Quest ID `{}` not found.", quest_id)).await?;
+        return Ok(());
+    };
+    let title = row[1].clone();
+    let max_slots = slots_from_cell(&row[3]);
+    let team_size = quest_team_size(row);
+
+    if team_size <= 1 {
+        ctx.say(format!("❌ **{}** is a solo quest. Each member can use `/take`.", title)).await?;
+        return Ok(());
+    }
+    if party.members.len() > team_size as usize {
+        ctx.say(format!("❌ **{}** allows teams of up to {}, your party has {}.", title, team_size, party.members.len())).await?;
+        return Ok(());
+    }
+
+    let ts = |s: &str| DateTime::parse_from_rfc3339(s).map(|dt| dt.timestamp()).unwrap_or(0);
+    if calculate_status(Utc::now().timestamp(), &ts(&row[5]), &ts(&row[8])) == QuestStatus::Ended {
+        ctx.say("❌ This quest has already ended (deadline passed).").await?;
+        return Ok(());
+    }
+
+    let active: Vec<&Vec<String>> = data.p_rows.iter().skip(1)
+        .filter(|p| p.len() >= 4 && p[0] == quest_id && p[3] != "DROPPED")
+        .collect();
+
+    let already: Vec<String> = party.members.iter()
+        .filter(|m| active.iter().any(|p| p[1] == m.user_id))
+        .map(|m| format!("<@{}>", m.user_id))
+        .collect();
+    if !already.is_empty() {
+        ctx.say(format!("❌ Already taken by: {}. They need to drop it first.", already.join(", "))).await?;
+        return Ok(());
+    }
+
+    let prerequisites = quest_prerequisites(row);
+    let locked: Vec<String> = party.members.iter()
+        .filter(|m| !missing_prerequisites(&prerequisites, &data.p_rows, &m.user_id).is_empty())
+        .map(|m| format!("<@{}>", m.user_id))
+        .collect();
+    if !locked.is_empty() {
+        ctx.say(format!("🔒 **{}** requires {} first. Not completed yet by: {}.", title, prerequisites.join(", "), locked.join(", "))).await?;
+        return Ok(());
+    }
+
+    let open = free_slots(active.len() as u32, max_slots);
+    if let Some(open) = open.filter(|open| party.members.len() as u32 > *open) {
+        ctx.say(format!("❌ Not enough slots for your party: {} open, {} needed.", open, party.members.len())).await?;
+        return Ok(());
+    }
+
+    // The checks above use the cached sheet, so the worker has the final say: it
+    // registers the whole team or nobody. Teammates are only told once it has.
+    let result_key = team_result_key(&quest_id, &party.party_id);
+    let mut con = ctx.data().redis_client.get_multiplexed_async_connection().await?;
+    let _: () = con.del(&result_key).await?;
+
+    let payload = TeamRegistrationPayload {
+        quest_id: quest_id.clone(),
+        team_id: party.party_id.clone(),
+        members: party.members.clone(),
+    };
+    produce_event(ctx, "TAKE_QUEST_TEAM", &payload).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    let teammates: Vec<String> = party.members.iter()
+        .filter(|m| m.user_id != user_id)
+        .map(|m| m.user_id.clone())
+        .collect();
+    let teammate_message = format!("🤝 Your party leader registered your team for **{}**. One `/submit` from any member completes it for everyone.", title);
+
+    match wait_for_team_result(&ctx.data().redis_client, &result_key, CONFIRM_WAIT_SECS).await? {
+        Some(Ok(())) => {
+            notify_users(
+                &ctx.serenity_context().http,
+                &ctx.data().redis_client,
+                &config,
+                &teammates,
+                NotificationCategory::StatusChanges,
+                &teammate_message,
+            ).await;
+
+            let remaining = match open {
+                Some(open) => format!("Remaining slots: {} of {}.", open.saturating_sub(party.members.len() as u32), slots_label(max_slots)),
+                None => "No slot limit.".to_string(),
+            };
+            ctx.say(format!("✅ Registered {} members for **{}**. {}", party.members.len(), title, remaining)).await?;
+        }
+        Some(Err(reason)) => {
+            ctx.say(format!("❌ Couldn't register your party for **{}**: {}. Nobody was registered.", title, reason)).await?;
+        }
+        None => {
+            ctx.say(format!("⏳ Registration for **{}** sent, but the sheet hasn't confirmed it yet. You'll get a DM once it does.", title)).await?;
+
+            let http = ctx.serenity_context().http.clone();
+            let redis_client = ctx.data().redis_client.clone();
+            tokio::spawn(async move {
+                let leader = [user_id];
+                match wait_for_team_result(&redis_client, &result_key, CONFIRM_DM_WAIT_SECS).await {
+                    Ok(Some(Ok(()))) => {
+                        notify_users(&http, &redis_client, &config, &teammates, NotificationCategory::StatusChanges, &teammate_message).await;
+                        notify_users(&http, &redis_client, &config, &leader, NotificationCategory::StatusChanges,
+                            &format!("✅ Your party is registered for **{}**.", title)).await;
+                    }
+                    Ok(Some(Err(reason))) => {
+                        notify_users(&http, &redis_client, &config, &leader, NotificationCategory::StatusChanges,
+                            &format!("❌ Couldn't register your party for **{}**: {}. Nobody was registered.", title, reason)).await;
+                    }
+                    Ok(None) => eprintln!("No result for team {} on quest {}", payload.team_id, payload.quest_id),
+                    Err(e) => eprintln!("Failed to wait for team {} on quest {}: {:?}", payload.team_id, payload.quest_id, e),
+                }
+            });
+        }
+    }
+    Ok(())
+}
@@ -1,36 +1,45 @@
 use std::collections::HashMap;
 
-use crate::cache::{get_cached_sheet_data, get_guild_config};
+use crate::cache::{fetch_submission_rows, get_cached_sheet_data, get_guild_config, quest_is_published, quest_prerequisites, quest_proof_kinds, quest_step_count, quest_team_size};
+use crate::proof::{validate_attachment, validate_link, validate_text};
+use crate::duplicates::{find_duplicate, perceptual_hash, record_proof, DuplicateMatch, ProofFingerprint};
+use crate::storage::sha256_hex;
 use crate::{Data, Error};
-use crate::models::{DeletePayload, Division, EditPayload, ProofPayload, QuestCategory, QuestCompleteMode, QuestPayload, RegistrationPayload
-                    };
+use crate::models::{DeletePayload, Division, EditPayload, GuildConfig, NotificationCategory, ProofKind, ProofPayload, ProofRecord, QuestCategory, QuestCompleteMode, PublishPayload, QuestPayload, RegistrationPayload,
+                    VoiceBindingPayload};
 use crate::kafka::produce_event;
-use common::{parse_wib, calculate_status, QuestStatus};
+use crate::confirm::confirm_with_reason;
+use crate::announce::{announce_quest, announce_quest_batch, announcement_embed, mark_announced, quest_payload_from_row, wait_until_published};
+use crate::notify::notify_users;
+use crate::import::{parse_import, validate_record};
+use common::{free_slots, has_free_slot, parse_slots, parse_wib, calculate_status, remaining_steps, slots_from_cell, slots_label, QuestStatus};
 use futures_util::{stream, Stream};
 use futures_util::StreamExt;
 use poise::Modal as _;
 use poise::CreateReply;
-use serenity::all::{Attachment, AutocompleteChoice, ChannelId, CreateAttachment, CreateEmbed, CreateEmbedFooter, CreateMessage, RoleId};
-use chrono::{DateTime, Utc};
+use serenity::all::{Attachment, AutocompleteChoice, ButtonStyle, Channel, ChannelId, CreateActionRow, CreateAllowedMentions, CreateAttachment, CreateButton, CreateEmbed, CreateEmbedFooter,
+                   CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, EditInteractionResponse, RoleId};
+use serenity::collector::ComponentInteractionCollector;
+use chrono::{DateTime, FixedOffset, Utc};
 
 type Context<'a> = poise::Context<'a, Data, Error>;
 
-async fn get_quest_and_participant_data(ctx: Context<'_>, quest_id: &str) -> Result<(i8, i8, Option<String>, Option<String>, String), Error> {
+async fn get_quest_and_participant_data(ctx: Context<'_>, quest_id: &str) -> Result<(Option<u32>, u32, Option<String>, Option<String>, String), Error> {
     let res = get_cached_sheet_data(ctx).await;
 
-    let mut max_slots: i8 = 0;
+    let mut max_slots: Option<u32> = Some(0);
     let mut schedule_iso: Option<String> = None;
     let mut deadline_iso: Option<String> = None;
     let mut quest_title = "Unknown Quest".to_string();
     let mut found = false;
-    let mut current_participants: i8 = 0;
+    let mut current_participants: u32 = 0;
 
     match res {
         Ok(data) => {
             for row in data.q_rows.iter().skip(1) {
-                if row.len() >= 9 && row[0].clone() == quest_id {
+                if row.len() >= 9 && row[0].clone() == quest_id && quest_is_published(row) {
                     quest_title = row.get(1).map(|s| s.as_str()).unwrap_or("Unknown").to_string();
-                    max_slots = row[3].parse::<i8>().unwrap_or(0);
+                    max_slots = slots_from_cell(&row[3]);
                     schedule_iso = Some(row[5].clone());
                     deadline_iso = Some(row[8].clone());
                     found = true;
@@ -74,7 +83,46 @@ pub fn determine_organizer(category: QuestCategory, division: Division, communit
         }
 }
 
-async fn autocomplete_quest_id<'a>(
+/// Prerequisite quest ids the user hasn't COMPLETED or VERIFIED yet.
+pub fn missing_prerequisites<'a>(prerequisites: &'a [String], p_rows: &[Vec<String>], user_id: &str) -> Vec<&'a String> {
+    prerequisites.iter()
+        .filter(|prereq| !p_rows.iter().any(|row| {
+            row.len() >= 4
+                && &row[0] == *prereq
+                && row[1] == user_id
+                && (row[3] == "COMPLETED" || row[3] == "VERIFIED")
+        }))
+        .collect()
+}
+
+/// Whether proof may be submitted: the quest is taken and not yet verified, failed
+/// or dropped, and the deadline (when set) hasn't passed.
+pub fn submission_open(status: &str, deadline: Option<i64>, now: i64) -> bool {
+    matches!(status, "ON_PROGRESS" | "CHECKED_IN" | "COMPLETED") && deadline.is_none_or(|end| now <= end)
+}
+
+/// Lists what changed between two (schedule, deadline, location) triples
+/// as old → new lines for participant notifications.
+pub fn summarize_quest_changes(old: (&str, &str, &str), new: (&str, &str, &str)) -> Vec<String> {
+    let fmt_time = |iso: &str| match DateTime::parse_from_rfc3339(iso) {
+        Ok(dt) => format!("<t:{}:f>", dt.timestamp()),
+        Err(_) => "TBA".to_string(),
+    };
+
+    let mut changes = Vec::new();
+    if old.0 != new.0 {
+        changes.push(format!("• 📅 Start Time: {} → {}", fmt_time(old.0), fmt_time(new.0)));
+    }
+    if old.1 != new.1 {
+        changes.push(format!("• ⏰ Deadline: {} → {}", fmt_time(old.1), fmt_time(new.1)));
+    }
+    if old.2 != new.2 {
+        changes.push(format!("• 📍 Location: {} → {}", old.2, new.2));
+    }
+    changes
+}
+
+pub async fn autocomplete_quest_id<'a>(
     ctx: Context<'_>,
     partial: &'a str,
 ) -> impl Stream<Item = AutocompleteChoice> + 'a {
@@ -84,6 +132,9 @@ async fn autocomplete_quest_id<'a>(
     let mode = match ctx.command().name.as_str() {
         "take" => QuestCompleteMode::Take,
         "view" => QuestCompleteMode::View,
+        "clone" | "roster" => QuestCompleteMode::All,
+        "preview" | "publish" => QuestCompleteMode::Draft,
+        "submissions" => QuestCompleteMode::Mine,
         _ => QuestCompleteMode::Submit,
     };
 
@@ -105,11 +156,13 @@ async fn autocomplete_quest_id<'a>(
                         }
                     }
 
+                    let user_id = ctx.author().id.to_string();
                     for row in data.q_rows.iter().skip(1) {
-                        if row.len() >= 9 {
+                        let prerequisites = quest_prerequisites(row);
+                        if row.len() >= 9 && quest_is_published(row) && missing_prerequisites(&prerequisites, &data.p_rows, &user_id).is_empty() {
                             let id = row[0].clone();
                             let title = row.get(1).map(|s| s.as_str()).unwrap_or("No Title").to_string();
-                            let slots = row[3].parse::<i8>().unwrap_or(0);
+                            let slots = slots_from_cell(&row[3]);
                             let schedule_str = row[5].clone();
                             let deadline_str = row[8].clone(); 
                             let filled = *counts.get(&id).unwrap_or(&0);
@@ -129,13 +182,10 @@ async fn autocomplete_quest_id<'a>(
 
                             let status = calculate_status(now, &start, &end);
 
-                            if (status == QuestStatus::Upcoming || status == QuestStatus::Ongoing) && filled < slots {
-                                let name = format!("{} ({} left) - {}", title, slots - filled, id);
-                                let name = if name.len() > 100 {
-                                    name[0..100].to_string()
-                                } else {
-                                    name
-                                };
+                            if (status == QuestStatus::Upcoming || status == QuestStatus::Ongoing) && has_free_slot(filled, slots) {
+                                let left = free_slots(filled, slots).map(|n| format!("{} left", n)).unwrap_or_else(|| "open".to_string());
+                                let name = format!("{} ({}) - {}", title, left, id);
+                                let name: String = name.chars().take(100).collect();
 
                                 if name.to_lowercase().contains(&partial.to_lowercase()) {
                                     choices.push(AutocompleteChoice::new(
@@ -146,17 +196,20 @@ async fn autocomplete_quest_id<'a>(
                         }
                     }
                 },
-                QuestCompleteMode::Submit => {
+                QuestCompleteMode::Submit | QuestCompleteMode::Mine => {
                     // Logic: Iterate Participants (User specific), lookup Quest Titles
                     let user_id = ctx.author().id.to_string();
                     
                     let mut titles = HashMap::new();
+                    let mut deadlines = HashMap::new();
                     for row in data.q_rows.iter().skip(1) {
                         if row.len() >= 2 {
                             titles.insert(
                                 row[0].clone(), 
                                 row.get(1).map(|s| s.as_str()).unwrap_or("Unknown").to_string()
                             );
+                            let deadline = row.get(8).and_then(|s| DateTime::parse_from_rfc3339(s).ok()).map(|dt| dt.timestamp());
+                            deadlines.insert(row[0].clone(), deadline);
                         }
                     }
 
@@ -166,7 +219,12 @@ async fn autocomplete_quest_id<'a>(
                             let u_id = row[1].clone();
                             let status = row[3].clone();
 
-                            if u_id == user_id && status == "ON_PROGRESS" {
+                            let listed = match mode {
+                                QuestCompleteMode::Mine => status != "DROPPED",
+                                _ => submission_open(&status, deadlines.get(&q_id).copied().flatten(), now),
+                            };
+
+                            if u_id == user_id && listed {
                                 let title = titles.get(&q_id).map(|s| s.as_str()).unwrap_or("Unknown").to_string();
                                 
                                 // What the user SEES
@@ -181,9 +239,23 @@ async fn autocomplete_quest_id<'a>(
                         }
                     }
                 },
-                QuestCompleteMode::View => {
+                QuestCompleteMode::Draft => {
+                    for row in data.q_rows.iter().skip(1) {
+                        if row.len() >= 9 && !quest_is_published(row) {
+                            let status = row.get(11).map(|s| s.as_str()).unwrap_or("");
+                            let name = format!("{} [{}] - {}", row[1], status, row[0]);
+                            let name: String = name.chars().take(100).collect();
+
+                            if name.to_lowercase().contains(&partial.to_lowercase()) {
+                                choices.push(AutocompleteChoice::new(name, row[0].clone()));
+                            }
+                        }
+                    }
+                },
+                QuestCompleteMode::View | QuestCompleteMode::All => {
+                    let include_ended = matches!(mode, QuestCompleteMode::All);
                     for row in data.q_rows.iter().skip(1) {
-                        if row.len() >= 9 {
+                        if row.len() >= 9 && (include_ended || quest_is_published(row)) {
                             let id = row[0].clone();
                             let title = row.get(1).map(|s| s.as_str()).unwrap_or("No Title").to_string();
                             let schedule_str = row[5].clone();
@@ -204,13 +276,9 @@ async fn autocomplete_quest_id<'a>(
 
                             let status = calculate_status(now, &start, &end);
 
-                            if status != QuestStatus::Ended {
+                            if include_ended || status != QuestStatus::Ended {
                                 let name = format!("{} - {}", title, id);
-                                let name = if name.len() > 100 {
-                                    name[0..100].to_string()
-                                } else {
-                                    name
-                                };
+                                let name: String = name.chars().take(100).collect();
 
                                 if name.to_lowercase().contains(&partial.to_lowercase()) {
                                     choices.push(AutocompleteChoice::new(
@@ -226,6 +294,35 @@ async fn autocomplete_quest_id<'a>(
     stream::iter(choices).take(25)
 }
 
+#[derive(Debug, poise::Modal)]
+#[name = "Side Quest Details"]
+struct QuestModal {
+    #[name = "Quest Name"]
+    #[placeholder = "Example: 5v5 MLBB Fun Match / KSICK"]
+    title: String,
+    
+    #[name = "Description & Platform / Location"]
+    #[paragraph]
+    #[placeholder = "Row 1: [Platform/Location - Required\nRow 2+: [Quest Description]"]
+    description_and_platform: String,
+
+    #[name = "Participant Slots"]
+    #[placeholder = "Example: 5, or \"unlimited\""]
+    slots: String,
+
+    #[name = "Start Time (YYYY-MM-DD HH:MM)"]
+    #[placeholder = "E.g: 2025-11-25 19:00"]
+    #[min_length = 16] 
+    #[max_length = 16]
+    schedule: String,
+
+    #[name = "Deadline (YYYY-MM-DD HH:MM)"]
+    #[placeholder = "Empty if same as start time"]
+    deadline: Option<String>,
+}
+
+// Every slash option is a parameter, so the argument count grows with the options.
+#[allow(clippy::too_many_arguments)]
 #[poise::command(slash_command, description_localized("en-US", "Create a new quest"), check = "crate::security::check_quest_role")] 
 pub async fn create(
     ctx: Context<'_>,
@@ -239,6 +336,25 @@ pub async fn create(
     #[description = "Community Name (Fill only if Community)"]
     community_name: Option<String>,
 
+    #[description = "Save as a draft instead of announcing it now"]
+    draft: Option<bool>,
+
+    #[description = "Members per team for a party quest (empty for solo)"]
+    #[min = 2]
+    #[max = 25]
+    team_size: Option<i8>,
+
+    #[description = "Quest IDs that must be completed first, comma-separated"]
+    prerequisites: Option<String>,
+
+    #[description = "Number of checkpoints that each need their own proof (empty for one)"]
+    #[min = 2]
+    #[max = 10]
+    steps: Option<i8>,
+
+    #[description = "Accepted proof, comma-separated: image, pdf, audio, link, text (default image)"]
+    proof_types: Option<String>,
+
 ) -> Result<(), Error> {
     let organizer_final = match determine_organizer(category, division, community_name) {
         Ok(org) => org,
@@ -248,36 +364,252 @@ pub async fn create(
         }
     };
 
+    let mut proof_kinds = Vec::new();
+    for name in proof_types.iter().flat_map(|p| p.split(',')).filter(|n| !n.trim().is_empty()) {
+        match ProofKind::parse(name) {
+            Some(kind) if !proof_kinds.contains(&kind) => proof_kinds.push(kind),
+            Some(_) => {}
+            None => {
+                let names = ProofKind::ALL.iter().map(|k| k.label().to_lowercase()).collect::<Vec<_>>().join(", ");
+                ctx.send(CreateReply::default().content(format!("❌ Unknown proof type `{}`. Use: {}", name.trim(), names)).ephemeral(true)).await?;
+                return Ok(());
+            }
+        }
+    }
+
+    let app_ctx = match ctx {
+        poise::Context::Application(app_ctx) => app_ctx,
+        _ => {
+            ctx.say("❌ Error: This command must be run as slash command.").await?;
+            return Ok(());
+        }
+    };
+
+    let modal_data = QuestModal::execute(app_ctx).await?;
+    
+    if let Some(data) = modal_data {
+        let options = NewQuestOptions {
+            category: format!("{:?}", category),
+            organizer_name: organizer_final,
+            cloned_from: None,
+            draft: draft.unwrap_or(false),
+            team_size: team_size.unwrap_or(0),
+            prerequisites: prerequisites
+                .map(|p| p.split(',').map(|id| id.trim().to_string()).filter(|id| !id.is_empty()).collect())
+                .unwrap_or_default(),
+            steps: steps.unwrap_or(0),
+            proof_kinds,
+        };
+        publish_new_quest(ctx, data, options).await?;
+    }
+
+    Ok(())
+}
+
+/// Quest settings chosen outside of the modal (slash options or the clone source).
+struct NewQuestOptions {
+    category: String,
+    organizer_name: String,
+    cloned_from: Option<String>,
+    draft: bool,
+    team_size: i8,
+    prerequisites: Vec<String>,
+    steps: i8,
+    proof_kinds: Vec<ProofKind>,
+}
+
+/// Validates a submitted quest modal, emits `CREATE_QUEST` and announces the quest,
+/// or only shows a private preview when saved as a draft.
+/// Shared by `/create` and `/quest clone`.
+async fn publish_new_quest(ctx: Context<'_>, data: QuestModal, options: NewQuestOptions) -> Result<(), Error> {
+    let NewQuestOptions { category, organizer_name, cloned_from, draft, team_size, prerequisites, steps, proof_kinds } = options;
+
     let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
     let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
 
-    #[derive(Debug, poise::Modal)]
-    #[name = "Side Quest Details"]
-    struct QuestModal {
-        #[name = "Quest Name"]
-        #[placeholder = "Example: 5v5 MLBB Fun Match / KSICK"]
-        title: String,
-        
-        #[name = "Description & Platform / Location"]
-        #[paragraph]
-        #[placeholder = "Row 1: [Platform/Location - Required\nRow 2+: [Quest Description]"]
-        description_and_platform: String,
+    let slots = match parse_slots(&data.slots) {
+        Ok(slots) => slots,
+        Err(err_msg) => {
+            ctx.say(format!("❌ {}", err_msg)).await?;
+            return Ok(());
+        }
+    };
 
-        #[name = "Participant Slots"]
-        #[placeholder = "Example: 5"]
-        slots: String,
+    let schedule_iso = match parse_wib(&data.schedule) {
+        Ok(iso) => iso,
+        Err(err_msg) => {
+            ctx.say(format!("❌ {}", err_msg)).await?;
+            return Ok(());
+        }
+    };
 
-        #[name = "Start Time (YYYY-MM-DD HH:MM)"]
-        #[placeholder = "E.g: 2025-11-25 19:00"]
-        #[min_length = 16] 
-        #[max_length = 16]
-        schedule: String,
+    let deadline_iso = match data.deadline {
+        Some(d) if !d.trim().is_empty() => {
+            match parse_wib(&d) {
+                Ok(iso) => iso,
+                Err(err_msg) => {
+                    ctx.say(format!("❌ Deadline Error: {}", err_msg)).await?;
+                    return Ok(());
+                }
+            }
+        },
+        _ => schedule_iso.clone(),
+    };
 
-        #[name = "Deadline (YYYY-MM-DD HH:MM)"]
-        #[placeholder = "Empty if same as start time"]
-        deadline: Option<String>,
+    let (description, platform) = {
+        let parts: Vec<&str> = data.description_and_platform.splitn(2, '\n').collect();
+        let platform = parts[0].trim().to_string();
+        let description = parts.get(1).map(|s| s.trim().to_string()).unwrap_or_default();
+        (description, platform)
+    };
+
+    if !prerequisites.is_empty() {
+        let data = get_cached_sheet_data(ctx).await?;
+        let unknown: Vec<&String> = prerequisites.iter()
+            .filter(|id| !data.q_rows.iter().skip(1).any(|row| row.first() == Some(*id)))
+            .collect();
+        if !unknown.is_empty() {
+            ctx.say(format!("❌ Unknown prerequisite quest ID(s): {}", unknown.iter().map(|id| format!("`{}`", id)).collect::<Vec<_>>().join(", "))).await?;
+            return Ok(());
+        }
     }
 
+    let quest_id = uuid::Uuid::new_v4().to_string();
+
+    let payload = QuestPayload {
+        quest_id: quest_id.clone(),
+        title: data.title.clone(),
+        description,
+        slots,
+        category,
+        organizer_name,
+        schedule: schedule_iso.clone(),
+        platform,
+        deadline: deadline_iso.clone(),
+        creator_id: ctx.author().id.to_string(),
+        cloned_from,
+        status: if draft { "DRAFT" } else { "PUBLISHED" }.to_string(),
+        team_size,
+        prerequisites,
+        steps,
+        proof_kinds,
+        season: config.active_season().map(|s| s.id.clone()).unwrap_or_default(),
+        publish_at: if draft { None } else { Some(Utc::now().with_timezone(&FixedOffset::east_opt(7 * 3600).unwrap()).to_rfc3339()) },
+    };
+
+    produce_event(ctx, "CREATE_QUEST", &payload).await?;
+
+    if draft {
+        ctx.send(CreateReply::default()
+            .content(format!("📝 Draft saved. Only quest givers can see it until you run `/quest publish {}`.", quest_id))
+            .embed(announcement_embed(&payload))
+            .ephemeral(true)
+        ).await?;
+        return Ok(());
+    }
+
+    announce_when_published(ctx, &config, &payload).await
+}
+
+/// Announces a quest once the worker has written it as published. If that takes too
+/// long, the scheduled announcement job posts it instead.
+async fn announce_when_published(ctx: Context<'_>, config: &GuildConfig, payload: &QuestPayload) -> Result<(), Error> {
+    let data = ctx.data();
+    if !wait_until_published(&data.sheets_hub, &data.google_sheet_id, &payload.quest_id).await {
+        ctx.send(CreateReply::default()
+            .content(format!("✅ **{}** saved. It will be announced as soon as the sheet is updated.", payload.title))
+            .ephemeral(true)
+        ).await?;
+        return Ok(());
+    }
+
+    if !mark_announced(&data.redis_client, &payload.quest_id).await? {
+        ctx.send(CreateReply::default().content(format!("✅ **{}** published.", payload.title)).ephemeral(true)).await?;
+        return Ok(());
+    }
+
+    let http = &ctx.serenity_context().http;
+    match announce_quest(http, &data.redis_client, config, data.participant_role_id, payload).await? {
+        Some(channel_id) => {
+            ctx.send(CreateReply::default()
+                .content(format!("✅ **{}** published and announced in <#{}>", payload.title, channel_id))
+                .ephemeral(true)
+            ).await?;
+        }
+        None => {
+            let ping_role = config.ping_role_id
+                .map(RoleId::new)
+                .unwrap_or(data.participant_role_id);
+            ctx.send(CreateReply::default()
+                .content(format!("<@&{}> A new quest is available!", ping_role))
+                .embed(announcement_embed(payload))
+            ).await?;
+        }
+    }
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("clone_quest", "drafts", "preview", "publish", "voice", "import"),
+    description_localized("en-US", "Quest utilities"),
+    check = "crate::security::check_quest_role"
+)]
+pub async fn quest(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command, rename = "clone", description_localized("en-US", "Create a new quest pre-filled from an existing one"))]
+pub async fn clone_quest(
+    ctx: Context<'_>,
+    #[description = "Quest to copy"]
+    #[autocomplete = "autocomplete_quest_id"]
+    quest_id: String,
+    #[description = "Save as a draft instead of announcing it now"]
+    draft: Option<bool>,
+) -> Result<(), Error> {
+    let data = match get_cached_sheet_data(ctx).await {
+        Ok(data) => data,
+        Err(e) => {
+            ctx.send(CreateReply::default().content(format!("❌ Failed to query sheet: {}", e)).ephemeral(true)).await?;
+            return Ok(());
+        }
+    };
+
+    // column mapping (0-based):
+    // 0: quest_id, 1: title, 2: category, 3: slots, 4: organizer_name,
+    // 5: schedule, 6: platform, 7: description, 8: deadline
+    let Some(row) = data.q_rows.iter().skip(1).find(|row| row.len() >= 9 && row[0] == quest_id) else {
+        ctx.send(CreateReply::default()
+            .content(format!("❌ Quest ID `{}` not found.", quest_id))
+            .ephemeral(true)).await?;
+        return Ok(());
+    };
+
+    let description_and_platform = if row[7].is_empty() {
+        row[6].clone()
+    } else {
+        format!("{}\n{}", row[6], row[7])
+    };
+
+    let defaults = QuestModal {
+        title: row[1].clone(),
+        description_and_platform,
+        slots: row[3].clone(),
+        schedule: String::new(),
+        deadline: None,
+    };
+    let options = NewQuestOptions {
+        category: row[2].clone(),
+        organizer_name: row[4].clone(),
+        cloned_from: Some(quest_id),
+        draft: draft.unwrap_or(false),
+        team_size: quest_team_size(row),
+        prerequisites: quest_prerequisites(row),
+        steps: quest_step_count(row),
+        proof_kinds: quest_proof_kinds(row),
+    };
+
     let app_ctx = match ctx {
         poise::Context::Application(app_ctx) => app_ctx,
         _ => {
@@ -286,104 +618,321 @@ pub async fn create(
         }
     };
 
-    let modal_data = QuestModal::execute(app_ctx).await?;
-    
-    if let Some(data) = modal_data {
-        let schedule_iso = match parse_wib(&data.schedule) {
+    if let Some(modal_data) = QuestModal::execute_with_defaults(app_ctx, defaults).await? {
+        publish_new_quest(ctx, modal_data, options).await?;
+    }
+
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "List draft and scheduled quests"))]
+pub async fn drafts(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let mut lines = Vec::new();
+    for row in data.q_rows.iter().skip(1) {
+        if row.len() < 9 || quest_is_published(row) {
+            continue;
+        }
+        let line = match (row.get(11).map(|s| s.as_str()), row.get(12).and_then(|s| DateTime::parse_from_rfc3339(s).ok())) {
+            (Some("SCHEDULED"), Some(at)) => format!("• **{}** — publishes <t:{}:f> — `{}`", row[1], at.timestamp(), row[0]),
+            _ => format!("• **{}** — draft — `{}`", row[1], row[0]),
+        };
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        ctx.say("📭 No drafts. Use `/create draft: True` to save one.").await?;
+        return Ok(());
+    }
+
+    let embed = CreateEmbed::default()
+        .title("📝 Draft Quests")
+        .description(lines.join("\n"))
+        .color(0x95A5A6)
+        .footer(CreateEmbedFooter::new("Use /quest preview or /quest publish <id>"));
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true)).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Privately preview how a draft will be announced"))]
+pub async fn preview(
+    ctx: Context<'_>,
+    #[description = "Draft quest"]
+    #[autocomplete = "autocomplete_quest_id"]
+    quest_id: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let Some(row) = data.q_rows.iter().skip(1).find(|row| row.len() >= 9 && row[0] == quest_id) else {
+        ctx.say(format!("❌ Quest ID `{}` not found.", quest_id)).await?;
+        return Ok(());
+    };
+
+    let payload = quest_payload_from_row(row);
+    ctx.send(CreateReply::default()
+        .content(format!("👀 Preview (status: `{}`)", if payload.status.is_empty() { "PUBLISHED" } else { &payload.status }))
+        .embed(announcement_embed(&payload))
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Publish a draft now or at a set time"))]
+pub async fn publish(
+    ctx: Context<'_>,
+    #[description = "Draft quest"]
+    #[autocomplete = "autocomplete_quest_id"]
+    quest_id: String,
+    #[description = "Publish at (YYYY-MM-DD HH:MM, WIB). Empty to publish now"]
+    at: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let Some(row) = data.q_rows.iter().skip(1).find(|row| row.len() >= 9 && row[0] == quest_id) else {
+        ctx.say(format!("❌ Quest ID `{}` not found.", quest_id)).await?;
+        return Ok(());
+    };
+    if quest_is_published(row) {
+        ctx.say(format!("❌ Quest `{}` is already published.", quest_id)).await?;
+        return Ok(());
+    }
+
+    if let Some(at) = at {
+        let publish_at = match parse_wib(&at) {
             Ok(iso) => iso,
             Err(err_msg) => {
                 ctx.say(format!("❌ {}", err_msg)).await?;
                 return Ok(());
             }
         };
+        let ts = DateTime::parse_from_rfc3339(&publish_at).map(|dt| dt.timestamp()).unwrap_or(0);
+        if ts <= Utc::now().timestamp() {
+            ctx.say("❌ Publish time must be in the future. Leave it empty to publish now.").await?;
+            return Ok(());
+        }
 
-        let deadline_iso = match data.deadline {
-            Some(d) if !d.trim().is_empty() => {
-                match parse_wib(&d) {
-                    Ok(iso) => iso,
-                    Err(err_msg) => {
-                        ctx.say(format!("❌ Deadline Error: {}", err_msg)).await?;
-                        return Ok(());
-                    }
-                }
-            },
-            _ => schedule_iso.clone(),
-        };
+        produce_event(ctx, "PUBLISH_QUEST", &PublishPayload { quest_id: quest_id.clone(), publish_at: Some(publish_at) }).await?;
+        ctx.say(format!("⏳ **{}** will be published and announced <t:{}:f>.", row[1], ts)).await?;
+        return Ok(());
+    }
 
-        let (description, platform) = {
-            let parts: Vec<&str> = data.description_and_platform.splitn(2, '\n').collect();
-            let platform = parts[0].trim().to_string();
-            let description = parts.get(1).map(|s| s.trim().to_string()).unwrap_or_default();
-            (description, platform)
-        };
+    produce_event(ctx, "PUBLISH_QUEST", &PublishPayload { quest_id: quest_id.clone(), publish_at: None }).await?;
 
-        let quest_id = uuid::Uuid::new_v4().to_string();
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    let mut payload = quest_payload_from_row(row);
+    payload.status = "PUBLISHED".to_string();
 
-        let payload = QuestPayload {
-            quest_id: quest_id.clone(),
-            title: data.title.clone(),
-            description: description,
-            slots: data.slots.clone().parse::<i8>().unwrap(),
-            category: format!("{:?}", category),
-            organizer_name: organizer_final,
-            schedule: schedule_iso.clone(),
-            platform: platform,
-            deadline: deadline_iso.clone(),
-            creator_id: ctx.author().id.to_string(),
-        };
+    announce_when_published(ctx, &config, &payload).await
+}
 
+#[poise::command(slash_command, description_localized("en-US", "Count time in a voice channel as attendance for a quest"))]
+pub async fn voice(
+    ctx: Context<'_>,
+    #[description = "Quest"]
+    #[autocomplete = "autocomplete_quest_id"]
+    quest_id: String,
+    #[description = "Voice channel to track. Leave empty to stop tracking"]
+    #[channel_types("Voice", "Stage")]
+    channel: Option<Channel>,
+    #[description = "Minutes needed between start time and deadline (default 30)"]
+    #[min = 1]
+    #[max = 1440]
+    minutes: Option<u32>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
 
-        produce_event(ctx, "CREATE_QUEST", &payload).await?;
+    let data = get_cached_sheet_data(ctx).await?;
+    let Some(row) = data.q_rows.iter().skip(1).find(|row| row.len() >= 9 && row[0] == quest_id) else {
+        ctx.say(format!("❌ Quest ID `{}` not found.", quest_id)).await?;
+        return Ok(());
+    };
 
-        let display_ts = DateTime::parse_from_rfc3339(&schedule_iso)
-            .unwrap()
-            .timestamp();
+    let Some(channel) = channel else {
+        produce_event(ctx, "SET_QUEST_VOICE", &VoiceBindingPayload { quest_id: quest_id.clone(), voice_channel_id: String::new(), minutes: 0 }).await?;
+        ctx.say(format!("✅ **{}** no longer tracks voice attendance.", row[1])).await?;
+        return Ok(());
+    };
 
-        let display_dl = DateTime::parse_from_rfc3339(&deadline_iso)
-            .unwrap()
-            .timestamp();
+    // Voice time is recorded as the proof of the quest's only step.
+    if quest_step_count(row) > 1 {
+        ctx.say("❌ Voice attendance only works for single-step quests.").await?;
+        return Ok(());
+    }
 
-        let ping_role = config.ping_role_id
-            .map(|id| RoleId::new(id))
-            .unwrap_or(ctx.data().participant_role_id);
+    let minutes = minutes.unwrap_or(crate::voice::DEFAULT_VOICE_MINUTES);
+    produce_event(ctx, "SET_QUEST_VOICE", &VoiceBindingPayload {
+        quest_id: quest_id.clone(),
+        voice_channel_id: channel.id().to_string(),
+        minutes,
+    }).await?;
+    ctx.say(format!(
+        "🎙️ Participants of **{}** who spend {} minutes in <#{}> between its start time and deadline will be completed automatically.",
+        row[1], minutes, channel.id()
+    )).await?;
+    Ok(())
+}
 
-        let embed = CreateEmbed::default()
-            .title(format!("⚔️ New Quest: {}", payload.title))
-            .description(&payload.description)
-            .field("📁 Category", &payload.category, true)
-            .field("🛡️ By", &payload.organizer_name, true)
-            .field("👥 Slots", &data.slots, true)
-            .field("📅 Start Time", format!("<t:{}:f>", display_ts), true)
-            .field("⏰ Deadline", format!("<t:{}:f>", display_dl), true)
-            .field("📍 Location", &payload.platform, true)
-            .field("ID", &quest_id, false)
-            .color(0xF1C40F)
-            .footer(CreateEmbedFooter::new("Use /take <id> to take the quest"));
+#[poise::command(slash_command, description_localized("en-US", "Create many quests from a CSV or JSON file"),
+                 check = "crate::security::check_admin")]
+pub async fn import(
+    ctx: Context<'_>,
+    #[description = "CSV (header row) or JSON (array of objects) with one quest per row"] file: Attachment,
+    #[description = "Save the quests as drafts (default: yes). No announces them right away"] draft: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
 
-        let message = CreateReply::default()
-            .content(format!("<@&{}> A new quest is available!", ping_role))
-            .embed(embed.clone());
+    if file.size > 1024 * 1024 {
+        ctx.say("❌ Import files can be at most 1 MB.").await?;
+        return Ok(());
+    }
+    let bytes = file.download().await?;
+    let Ok(text) = String::from_utf8(bytes) else {
+        ctx.say("❌ The file must be UTF-8 text.").await?;
+        return Ok(());
+    };
+    let records = match parse_import(&file.filename, &text) {
+        Ok(records) => records,
+        Err(e) => {
+            ctx.say(format!("❌ {}", e)).await?;
+            return Ok(());
+        }
+    };
 
-        if let Some(channel_id) = config.announcement_channel_id {
-            let target_channel = ChannelId::new(channel_id);
-            target_channel.send_message(&ctx.serenity_context().http, 
-                    CreateMessage::new()
-                    .content(format!("<@&{}> A new quest is available!", ping_role))
-                    .embed(embed)
-            ).await?;
-            
-            ctx.send(CreateReply::default()
-                .content(format!("✅ Quest created and announced in <#{}>", channel_id))
-                .ephemeral(true)
-            ).await?;
-        } else {
-            ctx.send(message).await?;
+    let data = get_cached_sheet_data(ctx).await?;
+    let known_ids: std::collections::HashSet<&str> = data.q_rows.iter().skip(1).filter_map(|row| row.first().map(|id| id.as_str())).collect();
+    let mut valid = Vec::new();
+    let mut problems = Vec::new();
+    for record in &records {
+        match validate_record(record, &known_ids) {
+            Ok(payload) => valid.push(payload),
+            Err(errors) => problems.push(format!("• **{}**: {}", record.label, errors.join("; "))),
+        }
+    }
+
+    let draft = draft.unwrap_or(true);
+    let mut preview = format!(
+        "📥 **{}**: {} of {} quest(s) are valid and will be {}.",
+        file.filename, valid.len(), records.len(), if draft { "saved as drafts" } else { "published and announced" }
+    );
+    if !problems.is_empty() {
+        preview.push_str(&format!("\n\n**{} row(s) with errors will be skipped:**\n", problems.len()));
+        let mut shown = 0;
+        for line in &problems {
+            // Leave room for the summary within the 2000-character message limit.
+            if preview.len() + line.len() > 1800 {
+                break;
+            }
+            preview.push_str(line);
+            preview.push('\n');
+            shown += 1;
+        }
+        if shown < problems.len() {
+            preview.push_str(&format!("…and {} more.", problems.len() - shown));
+        }
+    }
+    if valid.is_empty() {
+        ctx.say(preview).await?;
+        return Ok(());
+    }
+
+    let prefix = format!("{}_import_", ctx.id());
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}confirm", prefix)).label(format!("Import {}", valid.len())).style(ButtonStyle::Success),
+        CreateButton::new(format!("{}cancel", prefix)).label("Cancel").style(ButtonStyle::Secondary),
+    ]);
+    let handle = ctx.send(CreateReply::default().content(&preview).components(vec![buttons]).ephemeral(true)).await?;
+
+    let filter_prefix = prefix.clone();
+    let Some(interaction) = ComponentInteractionCollector::new(ctx.serenity_context())
+        .author_id(ctx.author().id)
+        .filter(move |i| i.data.custom_id.starts_with(&filter_prefix))
+        .timeout(std::time::Duration::from_secs(300))
+        .await
+    else {
+        handle.edit(ctx, CreateReply::default().content("⌛ Import timed out.").components(Vec::new())).await?;
+        return Ok(());
+    };
+    if interaction.data.custom_id.ends_with("cancel") {
+        interaction.create_response(ctx, CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new().content("❎ Import cancelled.").components(Vec::new())
+        )).await?;
+        return Ok(());
+    }
+    interaction.create_response(ctx, CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new().content(format!("⏳ Importing {} quest(s)…", valid.len())).components(Vec::new())
+    )).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    let creator_id = ctx.author().id.to_string();
+    let season = config.active_season().map(|s| s.id.clone()).unwrap_or_default();
+    // A failed send skips that row only; the rest of the file still goes through.
+    let mut created = Vec::new();
+    let mut failed = Vec::new();
+    for mut payload in valid {
+        payload.quest_id = uuid::Uuid::new_v4().to_string();
+        payload.creator_id = creator_id.clone();
+        payload.season = season.clone();
+        payload.status = if draft { "DRAFT" } else { "PUBLISHED" }.to_string();
+        match produce_event(ctx, "CREATE_QUEST", &payload).await {
+            Ok(()) => created.push(payload),
+            Err(e) => {
+                eprintln!("Failed to import quest {}: {:?}", payload.title, e);
+                failed.push(payload.title);
+            }
         }
     }
 
+    let mut next = if draft && !created.is_empty() { "Review them with `/quest drafts` and publish with `/quest publish`.".to_string() } else { String::new() };
+    if !draft && !created.is_empty() {
+        // Events are handled in order, so once the last quest is saved they all are.
+        // One post with a single ping instead of one per quest.
+        let last = created.last().map(|p| p.quest_id.as_str()).unwrap_or_default();
+        if !wait_until_published(&ctx.data().sheets_hub, &ctx.data().google_sheet_id, last).await {
+            eprintln!("Imported quests not saved yet; announcing anyway");
+        }
+        match announce_quest_batch(&ctx.serenity_context().http, &ctx.data().redis_client, &config, ctx.data().participant_role_id, &created).await {
+            Ok(Some(channel_id)) => next = format!("Announced in <#{}>.", channel_id),
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to announce imported quests: {:?}", e),
+        }
+    }
+
+    let created_titles: Vec<String> = created.iter().map(|p| p.title.clone()).collect();
+    let mut report = format!("{} Imported {} quest(s) from **{}**. {}", if failed.is_empty() { "✅" } else { "⚠️" }, created.len(), file.filename, next);
+    report.push_str(&import_title_list("Created", &created_titles, 900));
+    report.push_str(&import_title_list("Failed to send (try importing these again)", &failed, 1900 - report.len()));
+    interaction.edit_response(ctx, EditInteractionResponse::new().content(report)).await?;
     Ok(())
 }
 
+/// Bullet list of quest titles under a heading, cut off with "…and N more"
+/// once it would exceed `budget` characters. Empty when there are no titles.
+fn import_title_list(heading: &str, titles: &[String], budget: usize) -> String {
+    if titles.is_empty() {
+        return String::new();
+    }
+    let mut list = format!("\n\n**{} ({}):**\n", heading, titles.len());
+    let mut shown = 0;
+    for title in titles {
+        let line = format!("• {}\n", title);
+        if list.len() + line.len() > budget.saturating_sub(20) {
+            break;
+        }
+        list.push_str(&line);
+        shown += 1;
+    }
+    if shown < titles.len() {
+        list.push_str(&format!("…and {} more.", titles.len() - shown));
+    }
+    list
+}
+
 #[poise::command(slash_command, description_localized("en-US", "Edit an existing quest"), check = "crate::security::check_quest_role")] 
 pub async fn edit(
     ctx: Context<'_>,
@@ -399,6 +948,7 @@ pub async fn edit(
     let mut existing_schedule = String::new();
     let mut existing_deadline = String::new();
     let mut existing_description = String::new();
+    let mut participant_ids: Vec<String> = Vec::new();
     let mut found = false;
 
     let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
@@ -418,6 +968,12 @@ pub async fn edit(
                     break;
                 }
             }
+
+            for row in data.p_rows.iter().skip(1) {
+                if row.len() >= 4 && row[0] == quest_id && row[3] != "DROPPED" {
+                    participant_ids.push(row[1].clone());
+                }
+            }
         },
         Err(e) => {
             eprintln!("Sheet Error: {:?}", e);
@@ -445,6 +1001,7 @@ pub async fn edit(
         description_and_platform: Option<String>,
 
         #[name = "Participant Slots (optional)"]
+        #[placeholder = "A number, or \"unlimited\""]
         slots: Option<String>,
 
         #[name = "Start Time (YYYY-MM-DD HH:MM) (optional)"]
@@ -492,11 +1049,11 @@ pub async fn edit(
                 if t.is_empty() {
                     None
                 } else {
-                    match t.parse::<i8>() {
+                    match parse_slots(t) {
                         Ok(v) => Some(v),
-                        Err(_) => {
+                        Err(err_msg) => {
                             ctx.send(CreateReply::default()
-                                .content("❌ Invalid slots number.")
+                                .content(format!("❌ {}", err_msg))
                                 .ephemeral(true)).await?;
                             return Ok(());
                         }
@@ -549,7 +1106,7 @@ pub async fn edit(
         let final_title = title_opt.unwrap_or_else(|| existing_title.clone());
         let final_platform = platform_opt.unwrap_or_else(|| existing_platform.clone());
         let final_description = description_opt.unwrap_or_else(|| existing_description.clone());
-        let final_slots = slots_opt.unwrap_or_else(|| existing_slots.parse::<i8>().unwrap_or(0));
+        let final_slots = slots_opt.unwrap_or_else(|| slots_from_cell(&existing_slots));
         let final_schedule = schedule_opt.unwrap_or_else(|| existing_schedule.clone());
         let final_deadline = deadline_opt.unwrap_or_else(|| {
             if existing_deadline.is_empty() { final_schedule.clone() } else { existing_deadline.clone() }
@@ -567,6 +1124,26 @@ pub async fn edit(
 
         produce_event(ctx, "EDIT_QUEST", &edit_payload).await?;
 
+        let changes = summarize_quest_changes(
+            (&existing_schedule, &existing_deadline, &existing_platform),
+            (&final_schedule, &final_deadline, &final_platform),
+        );
+
+        if !changes.is_empty() && !participant_ids.is_empty() {
+            let dm_content = format!(
+                "✏️ The quest **{}** (`{}`) you took was updated:\n{}",
+                final_title, quest_id, changes.join("\n")
+            );
+            notify_users(
+                &ctx.serenity_context().http,
+                &ctx.data().redis_client,
+                &config,
+                &participant_ids,
+                NotificationCategory::StatusChanges,
+                &dm_content,
+            ).await;
+        }
+
         let display_ts = DateTime::parse_from_rfc3339(&final_schedule)
             .map(|dt| dt.timestamp())
             .unwrap_or(0);
@@ -582,7 +1159,7 @@ pub async fn edit(
         let embed = CreateEmbed::default()
                 .title(format!("✏️ Quest Edited: {}", final_title))
                 .description(&edit_payload.description)
-                .field("👥 Slots", format!("{}", final_slots), true)
+                .field("👥 Slots", slots_label(final_slots), true)
                 .field("📅 Start Time", format!("<t:{}:f>", display_ts), true)
                 .field("⏰ Deadline", format!("<t:{}:f>", display_dl), true)
                 .field("📍 Location", &edit_payload.platform, true)
@@ -622,6 +1199,7 @@ pub async fn delete(
     ctx.defer_ephemeral().await?;
     let lookup = get_cached_sheet_data(ctx).await;
     let mut quest_name = String::new();
+    let mut participant_ids: Vec<String> = Vec::new();
 
     let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
     let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
@@ -630,8 +1208,8 @@ pub async fn delete(
         Ok(data) => {
             let mut found = false;
                 for row in data.q_rows.iter().skip(1) {
-                    if row.len() >= 1 && row[0].clone() == quest_id {
-                        quest_name = row[1].clone();
+                    if !row.is_empty() && row[0] == quest_id {
+                        quest_name = row.get(1).cloned().unwrap_or_default();
                         found = true;
                         break;
                     }
@@ -641,6 +1219,12 @@ pub async fn delete(
                 ctx.say(format!("❌ Quest ID `{}` not found.", quest_id)).await?;
                 return Ok(());
             }
+
+            for row in data.p_rows.iter().skip(1) {
+                if row.len() >= 4 && row[0] == quest_id && row[3] != "DROPPED" {
+                    participant_ids.push(row[1].clone());
+                }
+            }
         }
         Err(e) => {
             ctx.say(format!("❌ Failed to query sheet: {}", e)).await?;
@@ -648,8 +1232,19 @@ pub async fn delete(
         }
     }
 
+    let prompt = format!(
+        "⚠️ Delete quest **{}** (`{}`)? This also removes its {} participant(s) and cannot be undone.",
+        quest_name, quest_id, participant_ids.len()
+    );
+
+    let reason = match confirm_with_reason(ctx, &prompt).await? {
+        Some(reason) => reason,
+        None => return Ok(()),
+    };
+
     let payload = DeletePayload {
         quest_id: quest_id.clone(),
+        reason: reason.clone(),
     };
 
     if let Err(e) = produce_event(ctx, "DELETE_QUEST", &payload).await {
@@ -657,11 +1252,24 @@ pub async fn delete(
         return Ok(());
     }
 
+    let dm_content = format!(
+        "📢 The quest **{}** (`{}`) you took was cancelled because: {}",
+        quest_name, quest_id, reason
+    );
+    notify_users(
+        &ctx.serenity_context().http,
+        &ctx.data().redis_client,
+        &config,
+        &participant_ids,
+        NotificationCategory::StatusChanges,
+        &dm_content,
+    ).await;
+
     let ping_role = config.ping_role_id
-            .map(|id| RoleId::new(id))
+            .map(RoleId::new)
             .unwrap_or(ctx.data().participant_role_id);
 
-        let message = format!("<@&{}> a quest `{}` with id `{}` has been deleted!", quest_name, quest_id, ping_role);
+        let message = format!("<@&{}> quest `{}` with id `{}` has been deleted!\nReason: {}", ping_role, quest_name, quest_id, reason);
 
         if let Some(channel_id) = config.announcement_channel_id {
             let target_channel = ChannelId::new(channel_id);
@@ -689,7 +1297,15 @@ pub async fn take(
     quest_id: String
 ) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
+    let message = take_quest(ctx, &quest_id).await?;
+    ctx.say(message).await?;
+    Ok(())
+}
 
+/// Registers the invoking user for a solo quest after the usual checks.
+/// Returns the message to show them; shared by `/take` and the quest board.
+pub async fn take_quest(ctx: Context<'_>, quest_id: &str) -> Result<String, Error> {
+    let quest_id = quest_id.to_string();
     let now = chrono::Utc::now().timestamp();
 
     let user_id = ctx.author().id.to_string();
@@ -710,19 +1326,35 @@ pub async fn take(
             let status = calculate_status(now, &quest_schedule, &quest_deadline);
 
             if status == QuestStatus::Ended {
-                ctx.say("❌ This quest has already ended (deadline passed).").await?;
-                return Ok(());
+                return Ok("❌ This quest has already ended (deadline passed).".to_string());
             }
             
             let mut retake = false;
             let participants_res = get_cached_sheet_data(ctx).await;
             if let Ok(data) = participants_res {
+                let quest_row = data.q_rows.iter().skip(1).find(|row| row.first() == Some(&quest_id));
+                let team_size = quest_row.map(|row| quest_team_size(row)).unwrap_or(1);
+                if team_size > 1 {
+                    return Ok(format!("❌ `{}` is a team quest (teams of up to {}). Form a party with `/party create` and use `/party take`.", quest_title, team_size));
+                }
+
+                let prerequisites = quest_row.map(|row| quest_prerequisites(row)).unwrap_or_default();
+                let missing = missing_prerequisites(&prerequisites, &data.p_rows, &user_id);
+                if !missing.is_empty() {
+                    let titles: Vec<String> = missing.iter()
+                        .map(|id| data.q_rows.iter().find(|row| row.first() == Some(*id))
+                            .and_then(|row| row.get(1).cloned())
+                            .unwrap_or_else(|| id.to_string()))
+                        .map(|title| format!("**{}**", title))
+                        .collect();
+                    return Ok(format!("🔒 `{}` is locked. Complete these quests first: {}", quest_title, titles.join(", ")));
+                }
+
                 for row in data.p_rows {
                     if row.len() >= 2 && row[0].clone() == quest_id && row[1].clone() == user_id {
                         let status = row.get(3).map(|s| s.as_str()).unwrap_or("");
                         if status != "DROPPED" {
-                            ctx.say("❌ You've taken this quest.").await?;
-                            return Ok(());
+                            return Ok("❌ You've taken this quest.".to_string());
                         }
                         // no need for else statement since status is DROPPED
                         retake = true;
@@ -730,10 +1362,13 @@ pub async fn take(
                 }
             }
 
-            if current_participants >= max_slots {
-                ctx.say(format!("❌ Quest `{}` is full. Available slots: {} of {}.", quest_title, max_slots - current_participants, max_slots)).await?;
-                return Ok(());
+            if !has_free_slot(current_participants, max_slots) {
+                return Ok(format!("❌ Quest `{}` is full ({} of {} slots taken).", quest_title, current_participants, slots_label(max_slots)));
             }
+            let remaining = |taken: u32| match free_slots(taken, max_slots) {
+                Some(left) => format!("Remaining slots: {} of {}.", left, slots_label(max_slots)),
+                None => "No slot limit.".to_string(),
+            };
 
             let payload = RegistrationPayload {
                 quest_id: quest_id.clone(),
@@ -743,17 +1378,16 @@ pub async fn take(
 
             if retake {
                 produce_event(ctx, "RETAKE_QUEST", &payload).await?;
-                ctx.say(format!("✅ Successfully taken the quest `{}`. Remaining slots: {} of {}.", quest_title, max_slots - current_participants, max_slots)).await?;
+                Ok(format!("✅ Successfully taken the quest `{}`. {}", quest_title, remaining(current_participants)))
             } else {
                 produce_event(ctx, "TAKE_QUEST", &payload).await?;
-                ctx.say(format!("✅ Successfully taken the quest `{}`. Remaining slots: {} of {}.", quest_title, max_slots - (current_participants + 1), max_slots)).await?;
+                Ok(format!("✅ Successfully taken the quest `{}`. {}", quest_title, remaining(current_participants + 1)))
             }
         },
         Err(e) => {
-            ctx.say(format!("❌ Failed to take quest: {}", e)).await?;
+            Ok(format!("❌ Failed to take quest: {}", e))
         }
     }
-    Ok(())
 }
 
 #[poise::command(slash_command, description_localized("en-US", "Drop a taken quest"),
@@ -796,7 +1430,7 @@ pub async fn drop(
                     let status = row[3].clone();
 
                     if q_id == quest_id && u_id == user_id {
-                        if status == "ON_PROGRESS" {
+                        if status == "ON_PROGRESS" || status == "CHECKED_IN" {
                             found_on_progress = true;
                             break;
                         } else {
@@ -833,44 +1467,250 @@ pub async fn submit(
     #[description = "Taken Quest"]
     #[autocomplete = "autocomplete_quest_id"]
     quest_id: String,
-    #[description = "Upload Proof"] proof_image: Attachment,
+    #[description = "Upload proof (image, PDF or audio)"] proof_file: Option<Attachment>,
+    #[description = "Link proof (itch.io, YouTube, Google Drive)"] link: Option<String>,
+    #[description = "Written proof"] text: Option<String>,
+    #[description = "Checkpoint this proof is for (multi-step quests)"]
+    #[min = 1]
+    #[max = 10]
+    step: Option<i8>,
 ) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
     let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
     let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
 
-    if let Some(ctype) = &proof_image.content_type {
-        if !ctype.starts_with("image/") {
-            ctx.say("❌ Please upload an image (jpg/png).").await?;
-            return Ok(());
-        }
-    } else {
-        ctx.say("❌ Invalid file.").await?;
+    let data = get_cached_sheet_data(ctx).await?;
+    let quest_row = data.q_rows.iter().skip(1).find(|row| row.first() == Some(&quest_id));
+    let total_steps = quest_row.map(|row| quest_step_count(row)).unwrap_or(1);
+    let allowed = quest_row.map(|row| quest_proof_kinds(row)).unwrap_or_else(|| vec![ProofKind::Image]);
+
+    let user_id = ctx.author().id.to_string();
+    let participant_status = data.p_rows.iter().skip(1)
+        .find(|row| row.len() >= 4 && row[0] == quest_id && row[1] == user_id)
+        .map(|row| row[3].as_str())
+        .unwrap_or("");
+    let deadline = quest_row.and_then(|row| row.get(8)).and_then(|s| DateTime::parse_from_rfc3339(s).ok()).map(|dt| dt.timestamp());
+    if quest_row.is_none() || !submission_open(participant_status, deadline, Utc::now().timestamp()) {
+        let reason = match participant_status {
+            "VERIFIED" => "Your proof for this quest has already been verified, so it can't be resubmitted.",
+            "ON_PROGRESS" | "CHECKED_IN" | "COMPLETED" => "The deadline has passed, so proof can no longer be submitted.",
+            "FAILED" => "This quest was marked failed, so proof can no longer be submitted.",
+            _ => "You haven't taken this quest. Use `/take` first.",
+        };
+        ctx.say(format!("❌ {}", reason)).await?;
         return Ok(());
     }
 
-    let image_bytes = proof_image.download().await?;
+    let step = match step {
+        Some(step) if step > total_steps => {
+            ctx.say(format!("❌ This quest only has {} step(s).", total_steps)).await?;
+            return Ok(());
+        }
+        Some(step) => step,
+        None if total_steps > 1 => {
+            ctx.say(format!("❌ This quest has {} steps. Pick the `step` this proof is for.", total_steps)).await?;
+            return Ok(());
+        }
+        None => 1,
+    };
 
-    let attachment =  CreateAttachment::bytes(image_bytes, &proof_image.filename);
+    let accepted = allowed.iter().map(|k| k.label()).collect::<Vec<_>>().join(", ");
+    let (proof_kind, proof, attachment, fingerprint) = match (proof_file, link, text) {
+        (Some(file), None, None) => {
+            let kind = match validate_attachment(&allowed, file.content_type.as_deref(), file.size) {
+                Ok(kind) => kind,
+                Err(msg) => {
+                    ctx.say(format!("❌ {} Accepted: {}.", msg, accepted)).await?;
+                    return Ok(());
+                }
+            };
+            let bytes = file.download().await?;
+            let fingerprint = ProofFingerprint {
+                sha256: sha256_hex(&bytes),
+                phash: if kind == ProofKind::Image { perceptual_hash(&file.proxy_url).await } else { None },
+            };
+            let content_type = file.content_type.as_deref().unwrap_or("application/octet-stream");
+            let location = match &ctx.data().proof_store {
+                Some(store) => match store.put(&bytes, &file.filename, content_type).await {
+                    Ok(stored) => stored.location,
+                    Err(e) => {
+                        eprintln!("Failed to store proof file for quest {}: {:?}", quest_id, e);
+                        file.url.clone()
+                    }
+                },
+                None => file.url.clone(),
+            };
+            (kind, location, Some(CreateAttachment::bytes(bytes, &file.filename)), Some(fingerprint))
+        }
+        (None, Some(link), None) if allowed.contains(&ProofKind::Link) => {
+            if let Err(msg) = validate_link(&link) {
+                ctx.say(format!("❌ {}", msg)).await?;
+                return Ok(());
+            }
+            (ProofKind::Link, link.trim().to_string(), None, None)
+        }
+        (None, None, Some(text)) if allowed.contains(&ProofKind::Text) => {
+            if let Err(msg) = validate_text(&text) {
+                ctx.say(format!("❌ {}", msg)).await?;
+                return Ok(());
+            }
+            (ProofKind::Text, text.trim().to_string(), None, None)
+        }
+        (None, None, None) => {
+            ctx.say(format!("❌ Attach a file, a link or text as proof. Accepted: {}.", accepted)).await?;
+            return Ok(());
+        }
+        (None, Some(_), None) | (None, None, Some(_)) => {
+            ctx.say(format!("❌ This quest doesn't accept that kind of proof. Accepted: {}.", accepted)).await?;
+            return Ok(());
+        }
+        _ => {
+            ctx.say("❌ Submit only one proof at a time: a file, a link or text.").await?;
+            return Ok(());
+        }
+    };
 
     let payload = ProofPayload {
         quest_id: quest_id.clone(),
-        user_id: ctx.author().id.to_string(),
-        proof_url: proof_image.url.clone(),
+        user_id: user_id.clone(),
+        proof_url: proof.clone(),
+        step,
+        total_steps,
+        proof_kind,
     };
 
     produce_event(ctx, "SUBMIT_PROOF", &payload).await?;
 
+    let step_label = if total_steps > 1 { format!(" (step {}/{})", step, total_steps) } else { String::new() };
+
+    let redis_client = &ctx.data().redis_client;
+    let duplicate = match &fingerprint {
+        Some(fingerprint) => find_duplicate(redis_client, fingerprint, &quest_id, &payload.user_id).await
+            .unwrap_or_else(|e| {
+                eprintln!("Duplicate proof check failed: {:?}", e);
+                None
+            }),
+        None => None,
+    };
+
+    let mut proof_link = proof.clone();
     if let Some(channel_id) = config.proof_channel_id {
         let target_channel = ChannelId::new(channel_id);
-        target_channel.send_files(
-            &ctx,
-            vec![attachment],
-            CreateMessage::new().content(format!("Proof for Quest ID: `{}` from `{}`", quest_id, ctx.author().name))
-        ).await?;
+        let mut header = format!("Proof for Quest ID: `{}`{} from `{}`", quest_id, step_label, ctx.author().name);
+        if let Some(duplicate) = &duplicate {
+            let (what, earlier) = match duplicate {
+                DuplicateMatch::Exact(record) => ("Same file as", record),
+                DuplicateMatch::Similar(record) => ("Looks like the image from", record),
+            };
+            header.push_str(&format!(
+                "\n⚠️ **Possible duplicate.** {} an earlier proof for quest `{}` by <@{}>: {}",
+                what, earlier.quest_id, earlier.user_id, earlier.link
+            ));
+        }
+        let message = match attachment {
+            Some(attachment) => {
+                target_channel.send_files(&ctx, vec![attachment], CreateMessage::new()
+                    .content(header)
+                    .allowed_mentions(CreateAllowedMentions::new())
+                ).await?
+            }
+            None => {
+                target_channel.send_message(&ctx, CreateMessage::new()
+                    .content(format!("{} ({})\n>>> {}", header, proof_kind.label(), proof))
+                    .allowed_mentions(CreateAllowedMentions::new())
+                ).await?
+            }
+        };
+        proof_link = message.link();
+    }
+
+    if let Some(fingerprint) = &fingerprint {
+        let record = ProofRecord { quest_id: quest_id.clone(), user_id: payload.user_id.clone(), link: proof_link };
+        if let Err(e) = record_proof(redis_client, fingerprint, &record).await {
+            eprintln!("Failed to record proof hashes for quest {}: {:?}", quest_id, e);
+        }
+    }
+
+    let mut submitted: Vec<i8> = fetch_submission_rows(&ctx.data().sheets_hub, &ctx.data().google_sheet_id).await
+        .unwrap_or_default()
+        .iter()
+        .filter(|row| row.len() >= 2 && row[0] == quest_id && row[1] == user_id)
+        .map(|row| row.get(4).and_then(|s| s.parse::<i8>().ok()).unwrap_or(1))
+        .collect();
+    let attempt = submitted.iter().filter(|s| **s == step).count() + 1;
+    let attempt_label = if attempt > 1 { format!(" (attempt {})", attempt) } else { String::new() };
+
+    if total_steps > 1 {
+        submitted.push(step);
+        let remaining = remaining_steps(total_steps, &submitted);
+        if !remaining.is_empty() {
+            let remaining = remaining.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", ");
+            ctx.say(format!("✅ Proof for step {}/{} of quest `{}` submitted{}. Steps left: {}.", step, total_steps, quest_id, attempt_label, remaining)).await?;
+            return Ok(());
+        }
+    }
+
+    ctx.say(format!("✅ Proof for quest `{}` has been successfully submitted{}.", quest_id, attempt_label)).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Show your proof submissions for a quest"),
+                 check = "crate::security::check_guild", check = "crate::security::check_participant_role")]
+pub async fn submissions(
+    ctx: Context<'_>,
+    #[description = "Quest"]
+    #[autocomplete = "autocomplete_quest_id"]
+    quest_id: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let user_id = ctx.author().id.to_string();
+    let data = ctx.data();
+
+    // Submissions column mapping (0-based):
+    // 0: quest_id, 1: user_id, 2: proof (URL or text), 3: timestamp, 4: step, 5: proof_kind, 6: attempt
+    let rows: Vec<Vec<String>> = fetch_submission_rows(&data.sheets_hub, &data.google_sheet_id).await?
+        .into_iter()
+        .filter(|row| row.len() >= 4 && row[0] == quest_id && row[1] == user_id)
+        .collect();
+
+    if rows.is_empty() {
+        ctx.say(format!("📭 You haven't submitted proof for quest `{}` yet.", quest_id)).await?;
+        return Ok(());
+    }
+
+    let sheet = get_cached_sheet_data(ctx).await?;
+    let title = sheet.q_rows.iter().skip(1)
+        .find(|row| row.first() == Some(&quest_id))
+        .and_then(|row| row.get(1).cloned())
+        .unwrap_or_else(|| quest_id.clone());
+    let status = sheet.p_rows.iter().skip(1)
+        .find(|row| row.len() >= 4 && row[0] == quest_id && row[1] == user_id)
+        .map(|row| row[3].clone())
+        .unwrap_or_else(|| "UNKNOWN".to_string());
+
+    let mut embed = CreateEmbed::default()
+        .title(format!("📜 Submissions: {}", title))
+        .description(format!("Status: **{}** · {} attempt(s)", status, rows.len()))
+        .color(0x3498DB);
+
+    // Embeds hold at most 25 fields; show the latest attempts.
+    for (i, row) in rows.iter().enumerate().skip(rows.len().saturating_sub(25)) {
+        let step = row.get(4).map(|s| s.as_str()).unwrap_or("1");
+        let attempt = row.get(6).filter(|a| !a.is_empty()).cloned()
+            .unwrap_or_else(|| rows[..=i].iter().filter(|r| r.get(4).map(|s| s.as_str()).unwrap_or("1") == step).count().to_string());
+        let kind = row.get(5).and_then(|k| ProofKind::parse(k)).unwrap_or(ProofKind::Image);
+        let submitted_at = DateTime::parse_from_rfc3339(&row[3])
+            .map(|dt| format!("<t:{}:f>", dt.timestamp()))
+            .unwrap_or_else(|_| row[3].clone());
+        let proof: String = row[2].chars().take(200).collect();
+        embed = embed.field(
+            format!("#{} · Step {} · {}", attempt, step, kind.label()),
+            format!("{}\n{}", submitted_at, proof),
+            false,
+        );
     }
 
-    ctx.say(format!("✅ Proof for quest `{}` has been successfully submitted.", quest_id)).await?;
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true)).await?;
     Ok(())
 }
 
@@ -891,15 +1731,36 @@ pub async fn view(
     let mut schedule_iso = String::new();
     let mut deadline_iso = String::new();
     let mut description = String::new();
+    let mut chain = Vec::new();
+    let mut steps = 1;
     let mut found = false;
 
     match res {
         Ok(data) => {
+            let user_id = ctx.author().id.to_string();
+            let title_of = |id: &str| data.q_rows.iter()
+                .find(|row| row.first().map(|s| s.as_str()) == Some(id))
+                .and_then(|row| row.get(1).cloned())
+                .unwrap_or_else(|| id.to_string());
+
             for row in data.q_rows.iter().skip(1) {
-                if row.len() >= 1 && row[0].clone() == quest_id {
+                if row.len() >= 1 && row[0].clone() == quest_id && quest_is_published(row) {
+                    steps = quest_step_count(row);
+                    let prerequisites = quest_prerequisites(row);
+                    let missing = missing_prerequisites(&prerequisites, &data.p_rows, &user_id);
+                    for prereq in &prerequisites {
+                        let mark = if missing.contains(&prereq) { "🔒" } else { "✅" };
+                        chain.push(format!("{} {} — `{}`", mark, title_of(prereq), prereq));
+                    }
+                    for next in data.q_rows.iter().skip(1) {
+                        if quest_is_published(next) && quest_prerequisites(next).contains(&quest_id) {
+                            chain.push(format!("➡️ Unlocks {} — `{}`", next.get(1).map(|s| s.as_str()).unwrap_or("No Title"), next[0]));
+                        }
+                    }
+
                     title = row.get(1).map(|v| v.as_str()).unwrap_or("No Title").to_string();
                     category = row.get(2).map(|v| v.as_str()).unwrap_or("Unknown").to_string();
-                    slots = row[3].parse::<i8>().unwrap_or(0).to_string();
+                    slots = slots_label(slots_from_cell(&row[3]));
                     organizer = row.get(4).map(|v| v.as_str()).unwrap_or("Unknown").to_string();
                     platform = row.get(6).map(|v| v.as_str()).unwrap_or("Unknown").to_string();
                     schedule_iso = row.get(5).map(|v| v.as_str()).unwrap_or("").to_string();
@@ -929,20 +1790,28 @@ pub async fn view(
         .unwrap()
         .timestamp();
 
+    let mut embed = CreateEmbed::default()
+        .title(format!("⚔️ Quest: {}", title))
+            .description(&description)
+            .field("📁 Category", &category, true)
+            .field("🛡️ By", &organizer, true)
+            .field("👥 Slots", &slots, true)
+            .field("📅 Start Time", format!("<t:{}:f>", display_ts), true)
+            .field("⏰ Deadline", format!("<t:{}:f>", display_dl), true)
+            .field("📍 Location", &platform, true)
+            .field("ID", &quest_id, false)
+            .color(0x3498DB)
+            .footer(CreateEmbedFooter::new("Use /take <id> to take this quest"));
+
+    if steps > 1 {
+        embed = embed.field("🪜 Steps", format!("{} checkpoints. Submit each with `/submit step:<n>`", steps), false);
+    }
+    if !chain.is_empty() {
+        embed = embed.field("🔗 Quest Chain", chain.join("\n"), false);
+    }
+
     ctx.send(CreateReply::default()
-        .embed(CreateEmbed::default()
-            .title(format!("⚔️ Quest: {}", title))
-                .description(&description)
-                .field("📁 Category", &category, true)
-                .field("🛡️ By", &organizer, true)
-                .field("👥 Slots", &slots, true)
-                .field("📅 Start Time", format!("<t:{}:f>", display_ts), true)
-                .field("⏰ Deadline", format!("<t:{}:f>", display_dl), true)
-                .field("📍 Location", &platform, true)
-                .field("ID", &quest_id, false)
-                .color(0x3498DB)
-                .footer(CreateEmbedFooter::new("Use /take <id> to take this quest"))
-        )
+        .embed(embed)
         .ephemeral(true)
     ).await?;
 
@@ -969,6 +1838,64 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_missing_prerequisites() {
+        let row = |q: &str, u: &str, status: &str| vec![q.to_string(), u.to_string(), "tag".to_string(), status.to_string()];
+        let p_rows = vec![
+            row("q1", "u1", "COMPLETED"),
+            row("q2", "u1", "ON_PROGRESS"),
+            row("q3", "u2", "VERIFIED"),
+        ];
+        let prereqs = vec!["q1".to_string(), "q2".to_string(), "q3".to_string()];
+
+        let missing = missing_prerequisites(&prereqs, &p_rows, "u1");
+        assert_eq!(missing, vec!["q2", "q3"]);
+        assert!(missing_prerequisites(&prereqs[..1], &p_rows, "u1").is_empty());
+    }
+
+    #[test]
+    fn test_import_title_list() {
+        assert!(import_title_list("Created", &[], 900).is_empty());
+        let titles = vec!["Alpha".to_string(), "Beta".to_string()];
+        assert_eq!(import_title_list("Created", &titles, 900), "\n\n**Created (2):**\n• Alpha\n• Beta\n");
+
+        let many: Vec<String> = (0..50).map(|i| format!("Quest number {}", i)).collect();
+        let list = import_title_list("Failed", &many, 200);
+        assert!(list.len() <= 200);
+        assert!(list.ends_with("more."));
+    }
+
+    #[test]
+    fn test_submission_open() {
+        assert!(submission_open("ON_PROGRESS", None, 100));
+        assert!(submission_open("CHECKED_IN", Some(100), 100));
+        assert!(submission_open("COMPLETED", Some(200), 100));
+        assert!(!submission_open("ON_PROGRESS", Some(50), 100));
+        assert!(!submission_open("COMPLETED", Some(50), 100));
+        for status in ["VERIFIED", "FAILED", "DROPPED", ""] {
+            assert!(!submission_open(status, None, 100));
+        }
+    }
+
+    #[test]
+    fn test_remaining_steps() {
+        assert_eq!(remaining_steps(3, &[2]), vec![1, 3]);
+        assert!(remaining_steps(3, &[1, 2, 3, 2]).is_empty());
+        assert!(remaining_steps(0, &[1]).is_empty());
+        assert_eq!(remaining_steps(1, &[]), vec![1]);
+    }
+
+    #[test]
+    fn test_summarize_quest_changes() {
+        let old = ("2025-11-20T19:00:00+07:00", "2025-11-20T21:00:00+07:00", "Discord");
+        let new = ("2025-11-21T19:00:00+07:00", "2025-11-20T21:00:00+07:00", "Aula Barat");
+        let changes = summarize_quest_changes(old, new);
+        assert_eq!(changes.len(), 2);
+        assert!(changes[0].contains("Start Time"));
+        assert!(changes[1].contains("Discord → Aula Barat"));
+        assert!(summarize_quest_changes(old, old).is_empty());
+    }
+
     #[test]
     fn test_determine_organizer_creative_valid() {
         let res = determine_organizer(QuestCategory::CreativeArts, Division::Illust, None);
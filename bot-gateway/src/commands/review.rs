@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::DateTime;
+use futures_util::StreamExt;
+use poise::CreateReply;
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::collector::ComponentInteractionCollector;
+
+use crate::cache::{fetch_submission_rows, get_cached_sheet_data, get_guild_config, quest_step_count};
+use crate::confirm::ReasonModal;
+use crate::kafka::produce_event;
+use crate::models::{Division, NotificationCategory, ProofKind, ReviewPayload};
+use crate::notify::notify_users;
+use crate::{Data, Error};
+
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+/// A submission waiting for a verifier: the latest attempt for its quest, user and step.
+#[derive(Debug, Clone)]
+pub struct PendingSubmission {
+    /// 1-based row in the Submissions tab.
+    pub row_number: usize,
+    pub quest_id: String,
+    pub user_id: String,
+    pub proof: String,
+    pub submitted_at: String,
+    pub step: i8,
+    pub attempt: String,
+    pub kind: ProofKind,
+}
+
+/// Unreviewed submissions, oldest first. Older attempts superseded by a resubmission
+/// and submissions from participants who are no longer active are left out.
+pub fn pending_submissions(s_rows: &[Vec<String>], p_rows: &[Vec<String>]) -> Vec<PendingSubmission> {
+    // Submissions column mapping (0-based):
+    // 0: quest_id, 1: user_id, 2: proof, 3: timestamp, 4: step, 5: proof_kind, 6: attempt, 7: review_status
+    let cell = |row: &Vec<String>, i: usize| row.get(i).cloned().unwrap_or_default();
+    let step_of = |row: &Vec<String>| cell(row, 4).parse::<i8>().unwrap_or(1);
+
+    let mut latest: HashMap<(String, String, i8), usize> = HashMap::new();
+    for (i, row) in s_rows.iter().enumerate().skip(1) {
+        if row.len() >= 4 {
+            latest.insert((row[0].clone(), row[1].clone(), step_of(row)), i);
+        }
+    }
+
+    let is_active = |quest_id: &str, user_id: &str| p_rows.iter().any(|p| {
+        p.len() >= 4 && p[0] == quest_id && p[1] == user_id && (p[3] == "ON_PROGRESS" || p[3] == "CHECKED_IN" || p[3] == "COMPLETED")
+    });
+
+    let mut pending: Vec<PendingSubmission> = latest.into_values()
+        .map(|i| (i, &s_rows[i]))
+        .filter(|(_, row)| cell(row, 7).is_empty() && is_active(&row[0], &row[1]))
+        .map(|(i, row)| PendingSubmission {
+            row_number: i + 1,
+            quest_id: row[0].clone(),
+            user_id: row[1].clone(),
+            proof: row[2].clone(),
+            submitted_at: row[3].clone(),
+            step: step_of(row),
+            attempt: Some(cell(row, 6)).filter(|a| !a.is_empty()).unwrap_or_else(|| "1".to_string()),
+            kind: ProofKind::parse(&cell(row, 5)).unwrap_or(ProofKind::Image),
+        })
+        .collect();
+    pending.sort_by_key(|p| p.row_number);
+    pending
+}
+
+/// Reviews done per verifier, most active first.
+pub fn reviewer_counts(s_rows: &[Vec<String>]) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for row in s_rows.iter().skip(1) {
+        if let Some(reviewer) = row.get(8).filter(|r| !r.is_empty()) {
+            *counts.entry(reviewer.clone()).or_insert(0) += 1;
+        }
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+fn review_embed(item: &PendingSubmission, position: usize, total: usize, title: &str, counts: &[(String, usize)]) -> CreateEmbed {
+    let submitted = DateTime::parse_from_rfc3339(&item.submitted_at)
+        .map(|dt| format!("<t:{}:R>", dt.timestamp()))
+        .unwrap_or_else(|_| item.submitted_at.clone());
+
+    let mut embed = CreateEmbed::default()
+        .title(format!("🧾 Review {}/{}", position + 1, total))
+        .field("Quest", format!("{} (`{}`)", title, item.quest_id), false)
+        .field("Participant", format!("<@{}>", item.user_id), true)
+        .field("Step", item.step.to_string(), true)
+        .field("Attempt", item.attempt.clone(), true)
+        .field("Submitted", submitted, true)
+        .field("Type", item.kind.label(), true)
+        .color(0xE67E22)
+        .footer(CreateEmbedFooter::new("Approve, Reject (with a reason) or Skip to the next one"));
+
+    let is_url = item.proof.starts_with("https://") || item.proof.starts_with("http://");
+    embed = match item.kind {
+        ProofKind::Image if is_url => embed.image(&item.proof),
+        ProofKind::Text => embed.description(format!(">>> {}", item.proof.chars().take(1000).collect::<String>())),
+        _ => embed.field("Proof", item.proof.chars().take(1000).collect::<String>(), false),
+    };
+
+    if !counts.is_empty() {
+        let summary = counts.iter().take(10)
+            .map(|(id, n)| format!("<@{}>: {}", id, n))
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field("📊 Reviews by verifier", summary, false);
+    }
+    embed
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("queue"),
+    description_localized("en-US", "Review submitted proofs"),
+    check = "crate::security::check_verifier"
+)]
+pub async fn review(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Work through pending submissions, oldest first"))]
+pub async fn queue(
+    ctx: Context<'_>,
+    #[description = "Only quests from this division"] division: Option<Division>,
+    #[description = "Only quests from this community"] community: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let data = ctx.data();
+    let sheet = get_cached_sheet_data(ctx).await?;
+    let s_rows = fetch_submission_rows(&data.sheets_hub, &data.google_sheet_id).await?;
+
+    // Quests column mapping (0-based): 0: quest_id, 1: title, 4: organizer_name
+    let organizer_filter = match (division, community) {
+        (Some(division), _) if !matches!(division, Division::None) => Some(format!("{:?}", division)),
+        (_, Some(community)) if !community.trim().is_empty() => Some(community.trim().to_string()),
+        _ => None,
+    };
+    let quests: HashMap<String, &Vec<String>> = sheet.q_rows.iter().skip(1)
+        .filter(|row| row.len() >= 5)
+        .map(|row| (row[0].clone(), row))
+        .collect();
+
+    let mut items: Vec<PendingSubmission> = pending_submissions(&s_rows, &sheet.p_rows).into_iter()
+        .filter(|item| match &organizer_filter {
+            Some(organizer) => quests.get(&item.quest_id).is_some_and(|q| q[4].eq_ignore_ascii_case(organizer)),
+            None => true,
+        })
+        .collect();
+    let mut counts = reviewer_counts(&s_rows);
+
+    if items.is_empty() {
+        ctx.say("🎉 No pending submissions.").await?;
+        return Ok(());
+    }
+
+    let title_of = |quest_id: &str| quests.get(quest_id).map(|q| q[1].clone()).unwrap_or_else(|| "Unknown Quest".to_string());
+    let render = |items: &[PendingSubmission], idx: usize, counts: &[(String, usize)]| {
+        review_embed(&items[idx], idx, items.len(), &title_of(&items[idx].quest_id), counts)
+    };
+
+    let prefix = format!("{}_review_", ctx.id());
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}prev", prefix)).label("Prev").style(ButtonStyle::Secondary),
+        CreateButton::new(format!("{}approve", prefix)).label("Approve").style(ButtonStyle::Success),
+        CreateButton::new(format!("{}reject", prefix)).label("Reject").style(ButtonStyle::Danger),
+        CreateButton::new(format!("{}skip", prefix)).label("Skip").style(ButtonStyle::Primary),
+    ]);
+
+    let mut idx = 0;
+    let handle = ctx.send(CreateReply::default()
+        .embed(render(&items, idx, &counts))
+        .components(vec![buttons.clone()])
+        .ephemeral(true)
+    ).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    let reviewer_id = ctx.author().id.to_string();
+    let ctx_serenity = ctx.serenity_context();
+    let filter_prefix = prefix.clone();
+    let mut collector = ComponentInteractionCollector::new(ctx_serenity)
+        .author_id(ctx.author().id)
+        .filter(move |i| i.data.custom_id.starts_with(&filter_prefix))
+        .timeout(Duration::from_secs(600))
+        .stream();
+
+    while let Some(interaction) = collector.next().await {
+        let action = interaction.data.custom_id.trim_start_matches(&prefix).to_string();
+        // The interaction still to be answered; the reject modal answers it itself.
+        let mut responder = None;
+
+        match action.as_str() {
+            "prev" => {
+                idx = if idx == 0 { items.len() - 1 } else { idx - 1 };
+                responder = Some(interaction);
+            }
+            "skip" => {
+                idx = (idx + 1) % items.len();
+                responder = Some(interaction);
+            }
+            "approve" | "reject" => {
+                let reason = if action == "reject" {
+                    let modal = poise::execute_modal_on_component_interaction::<ReasonModal>(
+                        ctx,
+                        interaction,
+                        None,
+                        Some(Duration::from_secs(300)),
+                    ).await?;
+                    match modal {
+                        Some(data) if !data.reason.trim().is_empty() => data.reason.trim().to_string(),
+                        _ => continue,
+                    }
+                } else {
+                    responder = Some(interaction);
+                    String::new()
+                };
+                let decision = if action == "approve" { "APPROVED" } else { "REJECTED" };
+
+                let item = items.remove(idx);
+                let payload = ReviewPayload {
+                    quest_id: item.quest_id.clone(),
+                    user_id: item.user_id.clone(),
+                    row_number: item.row_number,
+                    decision: decision.to_string(),
+                    reviewer_id: reviewer_id.clone(),
+                    reason: reason.clone(),
+                    total_steps: quests.get(&item.quest_id).map(|q| quest_step_count(q)).unwrap_or(1),
+                };
+                produce_event(ctx, "REVIEW_SUBMISSION", &payload).await?;
+
+                match counts.iter_mut().find(|(id, _)| *id == reviewer_id) {
+                    Some(entry) => entry.1 += 1,
+                    None => counts.push((reviewer_id.clone(), 1)),
+                }
+                counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+                let title = title_of(&item.quest_id);
+                let message = if decision == "APPROVED" {
+                    format!("✅ Your proof for **{}** (step {}) was approved.", title, item.step)
+                } else {
+                    format!("❌ Your proof for **{}** (step {}) was rejected: {}\nUse `/submit {}` to send a new one.", title, item.step, reason, item.quest_id)
+                };
+                notify_users(&ctx_serenity.http, &data.redis_client, &config, std::slice::from_ref(&item.user_id), NotificationCategory::StatusChanges, &message).await;
+
+                if idx >= items.len() {
+                    idx = 0;
+                }
+            }
+            _ => continue,
+        }
+
+        let (content, embeds, components) = if items.is_empty() {
+            ("🎉 Queue cleared, nothing left to review.", Vec::new(), Vec::new())
+        } else {
+            ("", vec![render(&items, idx, &counts)], vec![buttons.clone()])
+        };
+        match responder {
+            Some(interaction) => {
+                interaction.create_response(&ctx_serenity.http, CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new().content(content).embeds(embeds).components(components)
+                )).await?;
+            }
+            None => {
+                let mut reply = CreateReply::default().content(content).components(components);
+                reply.embeds = embeds;
+                handle.edit(ctx, reply).await?;
+            }
+        }
+
+        if items.is_empty() {
+            return Ok(());
+        }
+    }
+
+    handle.edit(ctx, CreateReply::default()
+        .content("⌛ Review session timed out. Run `/review queue` again to continue.")
+        .components(Vec::new())
+    ).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::s;
+
+    #[test]
+    fn test_pending_submissions() {
+        let header = s(&["quest_id", "user_id", "proof", "timestamp", "step", "proof_kind", "attempt", "review_status"]);
+        let s_rows = vec![
+            header,
+            s(&["q1", "u1", "https://a", "t1", "1", "IMAGE", "1"]),
+            s(&["q1", "u1", "https://b", "t2", "1", "IMAGE", "2"]),
+            s(&["q1", "u2", "https://c", "t3", "1", "IMAGE", "1", "APPROVED", "v1"]),
+            s(&["q2", "u3", "done", "t4", "1", "TEXT", "1"]),
+            s(&["q1", "u4", "https://d", "t5"]),
+        ];
+        let p_rows = vec![
+            s(&["q1", "u1", "u1#0", "COMPLETED"]),
+            s(&["q1", "u2", "u2#0", "VERIFIED"]),
+            s(&["q2", "u3", "u3#0", "DROPPED"]),
+            s(&["q1", "u4", "u4#0", "COMPLETED"]),
+        ];
+
+        let pending = pending_submissions(&s_rows, &p_rows);
+        let rows: Vec<usize> = pending.iter().map(|p| p.row_number).collect();
+        // Only the latest attempt of u1, skipping reviewed and dropped ones.
+        assert_eq!(rows, vec![3, 6]);
+        assert_eq!(pending[0].attempt, "2");
+        assert_eq!(pending[1].attempt, "1");
+        assert_eq!(pending[1].kind, ProofKind::Image);
+    }
+
+    #[test]
+    fn test_reviewer_counts() {
+        let s_rows = vec![
+            s(&["header"]),
+            s(&["q1", "u1", "p", "t", "1", "IMAGE", "1", "APPROVED", "v2"]),
+            s(&["q1", "u2", "p", "t", "1", "IMAGE", "1", "REJECTED", "v1"]),
+            s(&["q1", "u3", "p", "t", "1", "IMAGE", "1", "APPROVED", "v1"]),
+            s(&["q1", "u4", "p", "t", "1", "IMAGE", "1"]),
+        ];
+        assert_eq!(reviewer_counts(&s_rows), vec![("v1".to_string(), 2), ("v2".to_string(), 1)]);
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use common::{calculate_status, QuestStatus};
+use futures_util::StreamExt;
+use poise::CreateReply;
+use serenity::all::{
+    ButtonStyle, CreateActionRow, CreateAttachment, CreateButton, CreateEmbed, CreateInteractionResponse,
+    CreateInteractionResponseFollowup, CreateInteractionResponseMessage, CreateMessage,
+};
+use serenity::collector::ComponentInteractionCollector;
+
+use crate::cache::{fetch_submission_rows, get_cached_sheet_data, get_guild_config, quest_creator};
+use crate::confirm::confirm_with_reason;
+use crate::export::to_csv;
+use crate::kafka::produce_event;
+use crate::models::{NoShowPayload, NotificationCategory};
+use crate::notify::{notify_users, send_dm};
+use crate::{Data, Error};
+
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+/// Embed descriptions are capped at 4096 characters; longer rosters are cut and
+/// the CSV export has the full list.
+const MAX_ROSTER_LINES: usize = 40;
+
+#[derive(Debug, poise::Modal)]
+#[name = "Message Participants"]
+struct RosterMessageModal {
+    #[name = "Message"]
+    #[paragraph]
+    #[placeholder = "Sent by DM to everyone on the roster (except dropped)"]
+    #[max_length = 1500]
+    message: String,
+}
+
+/// One participant of a quest, for `/roster`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RosterEntry {
+    pub user_id: String,
+    pub user_tag: String,
+    pub status: String,
+    pub taken_at: String,
+    /// Latest submission time, if they submitted anything.
+    pub submitted_at: Option<String>,
+}
+
+impl RosterEntry {
+    /// Still in progress without any proof.
+    pub fn is_no_show(&self) -> bool {
+        self.status == "ON_PROGRESS" && self.submitted_at.is_none()
+    }
+}
+
+/// Participants of a quest in sign-up order, with their latest submission time.
+pub fn build_roster(quest_id: &str, p_rows: &[Vec<String>], s_rows: &[Vec<String>]) -> Vec<RosterEntry> {
+    let mut submitted: HashMap<&str, &str> = HashMap::new();
+    for row in s_rows.iter().skip(1) {
+        if row.len() >= 4 && row[0] == quest_id {
+            submitted.insert(row[1].as_str(), row[3].as_str());
+        }
+    }
+
+    p_rows.iter().skip(1)
+        .filter(|row| row.len() >= 4 && row[0] == quest_id)
+        .map(|row| RosterEntry {
+            user_id: row[1].clone(),
+            user_tag: row[2].clone(),
+            status: row[3].clone(),
+            taken_at: row.get(4).cloned().unwrap_or_default(),
+            submitted_at: submitted.get(row[1].as_str()).map(|s| s.to_string()),
+        })
+        .collect()
+}
+
+fn roster_csv(entries: &[RosterEntry]) -> String {
+    let mut rows = vec![["user_id", "user_tag", "status", "taken_at", "submitted_at"].map(String::from).to_vec()];
+    rows.extend(entries.iter().map(|e| vec![
+        e.user_id.clone(),
+        e.user_tag.clone(),
+        e.status.clone(),
+        e.taken_at.clone(),
+        e.submitted_at.clone().unwrap_or_default(),
+    ]));
+    to_csv(&rows)
+}
+
+fn roster_embed(title: &str, quest_id: &str, entries: &[RosterEntry]) -> CreateEmbed {
+    let ts = |iso: &str| DateTime::parse_from_rfc3339(iso)
+        .map(|dt| format!("<t:{}:R>", dt.timestamp()))
+        .unwrap_or_else(|_| "-".to_string());
+
+    let mut lines: Vec<String> = entries.iter().take(MAX_ROSTER_LINES)
+        .map(|e| format!(
+            "• <@{}> — `{}` · taken {} · submitted {}",
+            e.user_id, e.status, ts(&e.taken_at), e.submitted_at.as_deref().map(ts).unwrap_or_else(|| "-".to_string())
+        ))
+        .collect();
+    if entries.len() > MAX_ROSTER_LINES {
+        lines.push(format!("…and {} more. Export the CSV for the full list.", entries.len() - MAX_ROSTER_LINES));
+    }
+
+    let count = |status: &str| entries.iter().filter(|e| e.status == status).count();
+    CreateEmbed::default()
+        .title(format!("👥 Roster: {}", title))
+        .description(if lines.is_empty() { "Nobody has taken this quest yet.".to_string() } else { lines.join("\n") })
+        .field("🏃 In Progress", count("ON_PROGRESS").to_string(), true)
+        .field("📍 Checked In", count("CHECKED_IN").to_string(), true)
+        .field("✅ Completed", (count("COMPLETED") + count("VERIFIED")).to_string(), true)
+        .field("❌ Failed / Dropped", (count("FAILED") + count("DROPPED")).to_string(), true)
+        .field("ID", quest_id, false)
+        .color(0x3498DB)
+}
+
+#[poise::command(slash_command, description_localized("en-US", "See who took a quest and manage participants"),
+                 check = "crate::security::check_guild")]
+pub async fn roster(
+    ctx: Context<'_>,
+    #[description = "Quest"]
+    #[autocomplete = "crate::commands::quest::autocomplete_quest_id"]
+    quest_id: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let Some(row) = data.q_rows.iter().skip(1).find(|row| row.len() >= 9 && row[0] == quest_id) else {
+        ctx.say(format!("❌ Quest ID `{}` not found.", quest_id)).await?;
+        return Ok(());
+    };
+
+    let author_id = ctx.author().id.to_string();
+    if quest_creator(row) != Some(author_id.as_str()) && !crate::security::is_quest_giver(ctx).await? {
+        ctx.say("⛔ Only the quest's creator or staff with QuestRole can view its roster.").await?;
+        return Ok(());
+    }
+
+    let title = row[1].clone();
+    let ts = |s: &str| DateTime::parse_from_rfc3339(s).map(|dt| dt.timestamp()).unwrap_or(0);
+    let started = !matches!(calculate_status(Utc::now().timestamp(), &ts(&row[5]), &ts(&row[8])), QuestStatus::Upcoming | QuestStatus::Tba);
+
+    let s_rows = fetch_submission_rows(&ctx.data().sheets_hub, &ctx.data().google_sheet_id).await?;
+    let mut entries = build_roster(&quest_id, &data.p_rows, &s_rows);
+
+    let prefix = format!("{}_roster_", ctx.id());
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}dm", prefix)).label("DM All").style(ButtonStyle::Primary),
+        CreateButton::new(format!("{}noshow", prefix)).label("Mark No-Shows Failed").style(ButtonStyle::Danger),
+        CreateButton::new(format!("{}csv", prefix)).label("Export CSV").style(ButtonStyle::Secondary),
+    ]);
+
+    let handle = ctx.send(CreateReply::default()
+        .embed(roster_embed(&title, &quest_id, &entries))
+        .components(vec![buttons.clone()])
+        .ephemeral(true)
+    ).await?;
+
+    let ctx_serenity = ctx.serenity_context();
+    let filter_prefix = prefix.clone();
+    let mut collector = ComponentInteractionCollector::new(ctx_serenity)
+        .author_id(ctx.author().id)
+        .filter(move |i| i.data.custom_id.starts_with(&filter_prefix))
+        .timeout(Duration::from_secs(600))
+        .stream();
+
+    let reply = |content: String| CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new().content(content).ephemeral(true)
+    );
+
+    while let Some(interaction) = collector.next().await {
+        match interaction.data.custom_id.trim_start_matches(&prefix) {
+            "dm" => {
+                let recipients: Vec<&RosterEntry> = entries.iter().filter(|e| e.status != "DROPPED").collect();
+                if recipients.is_empty() {
+                    interaction.create_response(&ctx_serenity.http, reply("📭 Nobody to message.".to_string())).await?;
+                    continue;
+                }
+                let modal = poise::execute_modal_on_component_interaction::<RosterMessageModal>(
+                    ctx,
+                    interaction.clone(),
+                    None,
+                    Some(Duration::from_secs(300)),
+                ).await?;
+                let Some(modal) = modal.filter(|m| !m.message.trim().is_empty()) else {
+                    continue;
+                };
+
+                let content = format!("📣 **Message from the organizer of {}** (<@{}>):\n{}", title, author_id, modal.message.trim());
+                for entry in &recipients {
+                    send_dm(&ctx_serenity.http, &entry.user_id, CreateMessage::new().content(&content)).await;
+                }
+                interaction.create_followup(&ctx_serenity.http, CreateInteractionResponseFollowup::new()
+                    .content(format!("✅ Sent to {} participants.", recipients.len()))
+                    .ephemeral(true)
+                ).await?;
+            }
+            "noshow" => {
+                if !started {
+                    interaction.create_response(&ctx_serenity.http, reply("❌ The quest hasn't started yet.".to_string())).await?;
+                    continue;
+                }
+                let no_shows: Vec<String> = entries.iter().filter(|e| e.is_no_show()).map(|e| e.user_id.clone()).collect();
+                if no_shows.is_empty() {
+                    interaction.create_response(&ctx_serenity.http, reply("✅ Everyone in progress has submitted something.".to_string())).await?;
+                    continue;
+                }
+
+                interaction.defer(&ctx_serenity.http).await?;
+                let prompt = format!("⚠️ Mark {} participant(s) without any submission as `FAILED` on **{}**?", no_shows.len(), title);
+                let Some(reason) = confirm_with_reason(ctx, &prompt).await? else {
+                    continue;
+                };
+
+                produce_event(ctx, "MARK_NO_SHOWS", &NoShowPayload { quest_id: quest_id.clone(), user_ids: no_shows.clone() }).await?;
+
+                let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+                let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+                notify_users(
+                    &ctx_serenity.http,
+                    &ctx.data().redis_client,
+                    &config,
+                    &no_shows,
+                    NotificationCategory::StatusChanges,
+                    &format!("❌ You were marked as a no-show on **{}**. Reason: {}", title, reason),
+                ).await;
+
+                for entry in entries.iter_mut().filter(|e| no_shows.contains(&e.user_id)) {
+                    entry.status = "FAILED".to_string();
+                }
+                handle.edit(ctx, CreateReply::default()
+                    .embed(roster_embed(&title, &quest_id, &entries))
+                    .components(vec![buttons.clone()])
+                ).await?;
+            }
+            "csv" => {
+                let file = CreateAttachment::bytes(roster_csv(&entries).into_bytes(), format!("roster-{}.csv", quest_id));
+                interaction.create_response(&ctx_serenity.http, CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new().add_file(file).ephemeral(true)
+                )).await?;
+            }
+            _ => {}
+        }
+    }
+
+    handle.edit(ctx, CreateReply::default()
+        .embed(roster_embed(&title, &quest_id, &entries))
+        .components(Vec::new())
+    ).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::s;
+
+    #[test]
+    fn test_build_roster() {
+        let p_rows = vec![
+            s(&["quest_id", "user_id", "user_tag", "status", "timestamp"]),
+            s(&["q1", "u1", "one", "ON_PROGRESS", "2025-01-01T10:00:00+07:00"]),
+            s(&["q1", "u2", "two", "ON_PROGRESS", "2025-01-01T11:00:00+07:00"]),
+            s(&["q1", "u3", "three", "DROPPED", "2025-01-01T12:00:00+07:00"]),
+            s(&["q2", "u1", "one", "ON_PROGRESS"]),
+        ];
+        let s_rows = vec![
+            s(&["quest_id", "user_id", "proof", "timestamp"]),
+            s(&["q1", "u2", "https://a", "2025-01-02T00:00:00Z"]),
+        ];
+
+        let roster = build_roster("q1", &p_rows, &s_rows);
+        assert_eq!(roster.len(), 3);
+        assert!(roster[0].is_no_show());
+        assert!(!roster[1].is_no_show());
+        assert_eq!(roster[1].submitted_at.as_deref(), Some("2025-01-02T00:00:00Z"));
+        assert!(!roster[2].is_no_show());
+
+        let csv = roster_csv(&roster);
+        assert!(csv.starts_with("user_id,user_tag,status,taken_at,submitted_at\r\n"));
+        assert!(csv.contains("u2,two,ON_PROGRESS,2025-01-01T11:00:00+07:00,2025-01-02T00:00:00Z\r\n"));
+    }
+}
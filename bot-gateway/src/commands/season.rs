@@ -0,0 +1,290 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use common::parse_wib;
+use futures_util::{stream, Stream, StreamExt};
+use poise::CreateReply;
+use serenity::all::AutocompleteChoice;
+
+use crate::cache::{get_cached_sheet_data, get_guild_config, quest_season, set_guild_config};
+use crate::models::{GuildConfig, Season};
+use crate::{Data, Error};
+
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+/// Longest season id accepted by `/season start`.
+const MAX_SEASON_ID_LEN: usize = 20;
+
+/// Value of a `season` option that turns off season scoping.
+pub const ALL_SEASONS: &str = "all";
+
+fn ts(iso: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(iso).ok().map(|dt| dt.timestamp())
+}
+
+fn now_wib() -> String {
+    Utc::now().with_timezone(&FixedOffset::east_opt(7 * 3600).unwrap()).to_rfc3339()
+}
+
+/// Whether a Quests row belongs to the season. Quests stamped with a season id belong
+/// to that season; older quests and quests from templates are placed by when they
+/// were created (or scheduled, when the creation time is missing).
+pub fn quest_in_season(row: &[String], season: &Season) -> bool {
+    if let Some(id) = quest_season(row) {
+        return id.eq_ignore_ascii_case(&season.id);
+    }
+    // column mapping (0-based): 5: schedule, 9: created_at
+    let Some(at) = row.get(9).and_then(|s| ts(s)).or_else(|| row.get(5).and_then(|s| ts(s))) else {
+        return false;
+    };
+    let start = ts(&season.start).unwrap_or(i64::MIN);
+    let end = season.end.as_deref().and_then(ts).unwrap_or(i64::MAX);
+    at >= start && at < end
+}
+
+/// Quests and Participants rows (header rows kept) limited to a season's quests.
+/// `None` keeps every row.
+pub fn scope_to_season(q_rows: &[Vec<String>], p_rows: &[Vec<String>], season: Option<&Season>) -> (Vec<Vec<String>>, Vec<Vec<String>>) {
+    let Some(season) = season else {
+        return (q_rows.to_vec(), p_rows.to_vec());
+    };
+    let quests: Vec<Vec<String>> = q_rows.iter().take(1)
+        .chain(q_rows.iter().skip(1).filter(|row| quest_in_season(row, season)))
+        .cloned()
+        .collect();
+    let ids: HashSet<&str> = quests.iter().skip(1).filter_map(|row| row.first().map(|id| id.as_str())).collect();
+    let participants = p_rows.iter().take(1)
+        .chain(p_rows.iter().skip(1).filter(|row| row.first().is_some_and(|id| ids.contains(id.as_str()))))
+        .cloned()
+        .collect();
+    (quests, participants)
+}
+
+/// Turns a command's `season` option into the season to scope to: the active season
+/// when left empty, none for `all`, otherwise the named season.
+pub fn resolve_season(config: &GuildConfig, choice: Option<&str>) -> Result<Option<Season>, String> {
+    match choice.map(str::trim).filter(|c| !c.is_empty()) {
+        None => Ok(config.active_season().cloned()),
+        Some(c) if c.eq_ignore_ascii_case(ALL_SEASONS) => Ok(None),
+        Some(c) => config.seasons.iter()
+            .find(|s| s.id.eq_ignore_ascii_case(c))
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| format!("Unknown season `{}`. See `/season list`.", c)),
+    }
+}
+
+/// Checks a new season id: letters, digits, `-` and `_`, unique across seasons.
+fn validate_season_id(config: &GuildConfig, id: &str) -> Result<(), String> {
+    if id.is_empty() || id.chars().count() > MAX_SEASON_ID_LEN {
+        return Err(format!("The season ID must be 1 to {} characters.", MAX_SEASON_ID_LEN));
+    }
+    if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err("The season ID may only use letters, digits, `-` and `_`.".to_string());
+    }
+    if id.eq_ignore_ascii_case(ALL_SEASONS) || config.seasons.iter().any(|s| s.id.eq_ignore_ascii_case(id)) {
+        return Err(format!("Season `{}` already exists.", id));
+    }
+    Ok(())
+}
+
+/// Suffix for board and stats titles, e.g. " — Season GST25".
+pub fn season_label(season: Option<&Season>) -> String {
+    season.map(|s| format!(" — Season {}", s.id)).unwrap_or_default()
+}
+
+pub async fn autocomplete_season<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = AutocompleteChoice> + 'a {
+    let mut choices = Vec::new();
+
+    if let Some(guild_id) = ctx.guild_id() {
+        let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+        for season in config.seasons.iter().rev() {
+            let label = if season.end.is_none() { format!("{} (active)", season.id) } else { season.id.clone() };
+            if label.to_lowercase().contains(&partial.to_lowercase()) {
+                choices.push(AutocompleteChoice::new(label, season.id.clone()));
+            }
+        }
+    }
+    if ALL_SEASONS.contains(&partial.to_lowercase()) {
+        choices.push(AutocompleteChoice::new("All seasons", ALL_SEASONS));
+    }
+
+    stream::iter(choices).take(25)
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("start", "close", "list"),
+    description_localized("en-US", "Manage event seasons"),
+    check = "crate::security::check_admin"
+)]
+pub async fn season(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Close the active season and start a new one"))]
+pub async fn start(
+    ctx: Context<'_>,
+    #[description = "New season ID (e.g. GST25)"] id: String,
+    #[description = "Start time (YYYY-MM-DD HH:MM, WIB; default: now)"] start_date: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let mut config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+
+    let id = id.trim().to_string();
+    if let Err(msg) = validate_season_id(&config, &id) {
+        ctx.send(CreateReply::default().content(format!("❌ {}", msg)).ephemeral(true)).await?;
+        return Ok(());
+    }
+
+    let start = match start_date.filter(|d| !d.trim().is_empty()) {
+        Some(d) => match parse_wib(&d) {
+            Ok(iso) => iso,
+            Err(msg) => {
+                ctx.send(CreateReply::default().content(format!("❌ {}", msg)).ephemeral(true)).await?;
+                return Ok(());
+            }
+        },
+        None => now_wib(),
+    };
+
+    // Seasons don't overlap: a new one starts after the active season started, or after the last one ended.
+    if let Some(last) = config.seasons.last() {
+        let overlaps = match &last.end {
+            Some(end) => ts(&start) < ts(end),
+            None => ts(&start) <= ts(&last.start),
+        };
+        if overlaps {
+            ctx.send(CreateReply::default()
+                .content(format!("❌ Season `{}` must start after season `{}`.", id, last.id))
+                .ephemeral(true)
+            ).await?;
+            return Ok(());
+        }
+    }
+
+    let previous = config.active_season().map(|s| s.id.clone());
+    if let Some(active) = config.seasons.iter_mut().rev().find(|s| s.end.is_none()) {
+        active.end = Some(start.clone());
+    }
+    config.seasons.push(Season { id: id.clone(), start: start.clone(), end: None });
+
+    set_guild_config(ctx, guild_id.get(), &config).await?;
+
+    let closed = previous.map(|p| format!(" Season `{}` is closed.", p)).unwrap_or_default();
+    ctx.send(CreateReply::default()
+        .content(format!("🏁 Season **{}** started <t:{}:f>.{}", id, ts(&start).unwrap_or(0), closed))
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Close the active season without starting another"))]
+pub async fn close(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let mut config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+
+    let Some(active) = config.seasons.iter_mut().rev().find(|s| s.end.is_none()) else {
+        ctx.send(CreateReply::default().content("❌ No season is running.").ephemeral(true)).await?;
+        return Ok(());
+    };
+    active.end = Some(now_wib());
+    let id = active.id.clone();
+
+    set_guild_config(ctx, guild_id.get(), &config).await?;
+
+    ctx.send(CreateReply::default()
+        .content(format!("✅ Season **{}** closed. Boards and stats now show every quest until a new season starts.", id))
+        .ephemeral(true)
+    ).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "List seasons and their quest counts"))]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    if config.seasons.is_empty() {
+        ctx.say("📭 No seasons yet. Start one with `/season start`.").await?;
+        return Ok(());
+    }
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let lines: Vec<String> = config.seasons.iter().rev()
+        .map(|season| {
+            let quests = data.q_rows.iter().skip(1).filter(|row| quest_in_season(row, season)).count();
+            let end = match &season.end {
+                Some(end) => format!("<t:{}:d>", ts(end).unwrap_or(0)),
+                None => "now".to_string(),
+            };
+            let icon = if season.end.is_none() { "🟢" } else { "⚪" };
+            format!("{} **{}** — <t:{}:d> → {} · {} quests", icon, season.id, ts(&season.start).unwrap_or(0), end, quests)
+        })
+        .collect();
+
+    ctx.say(format!("**📅 Seasons**\n{}", lines.join("\n"))).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::s;
+
+    fn quest(id: &str, created_at: &str, season: &str) -> Vec<String> {
+        let mut row = s(&[id, "Title", "Community", "5", "GST", "", "", "", "", created_at]);
+        row.resize(20, String::new());
+        row.push(season.to_string());
+        row
+    }
+
+    #[test]
+    fn test_scope_to_season() {
+        let gst24 = Season { id: "GST24".into(), start: "2024-01-01T00:00:00+07:00".into(), end: Some("2025-01-01T00:00:00+07:00".into()) };
+        let gst25 = Season { id: "GST25".into(), start: "2025-01-01T00:00:00+07:00".into(), end: None };
+        let q_rows = vec![
+            s(&["quest_id"]),
+            quest("old", "2024-06-01T10:00:00+07:00", ""),
+            quest("new", "2025-02-01T10:00:00+07:00", ""),
+            // Stamped quests stay in their season whatever their dates say.
+            quest("stamped", "2025-02-01T10:00:00+07:00", "GST24"),
+        ];
+        let p_rows = vec![s(&["quest_id", "user_id"]), s(&["old", "u1"]), s(&["new", "u1"]), s(&["stamped", "u2"])];
+
+        let (quests, participants) = scope_to_season(&q_rows, &p_rows, Some(&gst24));
+        assert_eq!(quests.iter().map(|r| r[0].as_str()).collect::<Vec<_>>(), vec!["quest_id", "old", "stamped"]);
+        assert_eq!(participants.len(), 3);
+
+        let (quests, participants) = scope_to_season(&q_rows, &p_rows, Some(&gst25));
+        assert_eq!(quests.iter().map(|r| r[0].as_str()).collect::<Vec<_>>(), vec!["quest_id", "new"]);
+        assert_eq!(participants, vec![s(&["quest_id", "user_id"]), s(&["new", "u1"])]);
+
+        assert_eq!(scope_to_season(&q_rows, &p_rows, None).0.len(), 4);
+    }
+
+    #[test]
+    fn test_resolve_and_validate_season() {
+        let mut config = GuildConfig::default();
+        assert_eq!(resolve_season(&config, None), Ok(None));
+
+        config.seasons = vec![
+            Season { id: "GST24".into(), start: "2024-01-01T00:00:00+07:00".into(), end: Some("2025-01-01T00:00:00+07:00".into()) },
+            Season { id: "GST25".into(), start: "2025-01-01T00:00:00+07:00".into(), end: None },
+        ];
+        assert_eq!(resolve_season(&config, None).unwrap().unwrap().id, "GST25");
+        assert_eq!(resolve_season(&config, Some("gst24")).unwrap().unwrap().id, "GST24");
+        assert_eq!(resolve_season(&config, Some("all")), Ok(None));
+        assert!(resolve_season(&config, Some("GST23")).is_err());
+
+        assert!(validate_season_id(&config, "GST26").is_ok());
+        assert!(validate_season_id(&config, "gst25").is_err());
+        assert!(validate_season_id(&config, "all").is_err());
+        assert!(validate_season_id(&config, "GST 26").is_err());
+        assert!(validate_season_id(&config, "").is_err());
+    }
+}
@@ -1,10 +1,81 @@
-use crate::cache::get_cached_sheet_data;
+use crate::cache::{fetch_submission_rows, get_cached_sheet_data, get_guild_config, quest_step_count};
+use crate::commands::season::{resolve_season, scope_to_season, season_label};
+use crate::commands::list::paginate_embeds;
 use crate::{Data, Error};
 use crate::models::StatsResult;
+use chrono::DateTime;
 use poise::serenity_prelude as serenity;
 use poise::CreateReply;
 use std::collections::HashMap;
 
+/// Points for each verified step of a quest.
+pub const POINTS_PER_STEP: u32 = 10;
+
+/// Points a participation is worth: earned once VERIFIED, pending while COMPLETED
+/// and awaiting review, nothing otherwise.
+pub fn quest_points(status: &str, steps: i8) -> u32 {
+    match status {
+        "VERIFIED" | "COMPLETED" => POINTS_PER_STEP * steps.max(1) as u32,
+        _ => 0,
+    }
+}
+
+/// One quest a member took, for `/history`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub quest_id: String,
+    pub title: String,
+    pub status: String,
+    pub taken_at: String,
+    /// Latest submission time and proof (URL or text), if any.
+    pub submitted_at: Option<String>,
+    pub proof: Option<String>,
+    /// Earned points (VERIFIED), or the points waiting on review (COMPLETED).
+    pub points: u32,
+}
+
+/// Every quest the user took (any status), newest first.
+pub fn build_history(user_id: &str, q_rows: &[Vec<String>], p_rows: &[Vec<String>], s_rows: &[Vec<String>]) -> Vec<HistoryEntry> {
+    let quests: HashMap<&str, &Vec<String>> = q_rows.iter().skip(1)
+        .filter(|row| row.len() >= 2)
+        .map(|row| (row[0].as_str(), row))
+        .collect();
+
+    // Submissions are appended in order, so the last matching row is the latest attempt.
+    let mut latest_submission: HashMap<&str, &Vec<String>> = HashMap::new();
+    for row in s_rows.iter().skip(1) {
+        if row.len() >= 4 && row[1] == user_id {
+            latest_submission.insert(row[0].as_str(), row);
+        }
+    }
+
+    let mut entries: Vec<HistoryEntry> = p_rows.iter().skip(1)
+        .filter(|row| row.len() >= 4 && row[1] == user_id)
+        .map(|row| {
+            let quest = quests.get(row[0].as_str());
+            let steps = quest.map(|q| quest_step_count(q)).unwrap_or(1);
+            let submission = latest_submission.get(row[0].as_str());
+            HistoryEntry {
+                quest_id: row[0].clone(),
+                title: quest.map(|q| q[1].clone()).unwrap_or_else(|| "Unknown Quest".to_string()),
+                status: row[3].clone(),
+                taken_at: row.get(4).cloned().unwrap_or_default(),
+                submitted_at: submission.map(|s| s[3].clone()),
+                proof: submission.map(|s| s[2].clone()),
+                points: quest_points(&row[3], steps),
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| b.taken_at.cmp(&a.taken_at));
+    entries
+}
+
+fn fmt_date(iso: &str) -> String {
+    DateTime::parse_from_rfc3339(iso)
+        .map(|dt| format!("<t:{}:d>", dt.timestamp()))
+        .unwrap_or_else(|_| "-".to_string())
+}
+
 type Context<'a> = poise::Context<'a, Data, Error>;
 
 pub fn calculate_stats(
@@ -44,8 +115,8 @@ pub fn calculate_stats(
                     completed += 1;
                 } else if status.contains("FAILED") {
                     failed += 1;
-                } else if status.contains("ON_PROGRESS") {
-                    status = "ON PROGRESS".to_string();
+                } else if status.contains("ON_PROGRESS") || status.contains("CHECKED_IN") {
+                    status = status.replace('_', " ");
                     active += 1;
                     list_str.push_str(&format!(
                         "**{}**\n├ 🆔 ID: `{}`\n├ 🛡️ Organizer: {}\n└ 📌 Status: `{}`\n\n", 
@@ -60,21 +131,36 @@ pub fn calculate_stats(
 }
 
 #[poise::command(slash_command, description_localized("en-US", "View your personal status"), check = "crate::security::check_guild")]
-pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn stats(
+    ctx: Context<'_>,
+    #[description = "Season ID, or \"all\" (default: the active season)"]
+    #[autocomplete = "crate::commands::season::autocomplete_season"]
+    season: Option<String>,
+) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
 
     let user_id = ctx.author().id.to_string();
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    let season = match resolve_season(&config, season.as_deref()) {
+        Ok(season) => season,
+        Err(msg) => {
+            ctx.say(format!("❌ {}", msg)).await?;
+            return Ok(());
+        }
+    };
 
     let result = get_cached_sheet_data(ctx).await;
 
     match result {
         Ok(data) => {
-            let mut stats = calculate_stats(&user_id, &data.q_rows, &data.p_rows);
+            let (q_rows, p_rows) = scope_to_season(&data.q_rows, &data.p_rows, season.as_ref());
+            let mut stats = calculate_stats(&user_id, &q_rows, &p_rows);
 
             let dm_channel = ctx.author().create_dm_channel(&ctx).await?;
             
             let embed = serenity::CreateEmbed::new()
-                .title(format!("📊 User Stats: {}", ctx.author().name))
+                .title(format!("📊 User Stats: {}{}", ctx.author().name, season_label(season.as_ref())))
                 .field("🔥 Active Quests", format!("{}", stats.active), true)
                 .field("✅ Completed", format!("{}", stats.completed), true)
                 .field("❌ Failed", format!("{}", stats.failed), true)
@@ -105,9 +191,86 @@ pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+#[poise::command(slash_command, description_localized("en-US", "Show every quest a member has taken"), check = "crate::security::check_guild")]
+pub async fn history(
+    ctx: Context<'_>,
+    #[description = "Member to look up (staff only; defaults to you)"] user: Option<serenity::User>,
+    #[description = "Season ID, or \"all\" (default: the active season)"]
+    #[autocomplete = "crate::commands::season::autocomplete_season"]
+    season: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let target = user.unwrap_or_else(|| ctx.author().clone());
+    if target.id != ctx.author().id && !crate::security::is_quest_giver(ctx).await? {
+        ctx.say("⛔ Only staff can view other members' history.").await?;
+        return Ok(());
+    }
+
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let config = get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    let season = match resolve_season(&config, season.as_deref()) {
+        Ok(season) => season,
+        Err(msg) => {
+            ctx.say(format!("❌ {}", msg)).await?;
+            return Ok(());
+        }
+    };
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let s_rows = fetch_submission_rows(&ctx.data().sheets_hub, &ctx.data().google_sheet_id).await?;
+    let (q_rows, p_rows) = scope_to_season(&data.q_rows, &data.p_rows, season.as_ref());
+    let entries = build_history(&target.id.to_string(), &q_rows, &p_rows, &s_rows);
+
+    if entries.is_empty() {
+        let when = season.as_ref().map(|s| format!(" in season {}", s.id)).unwrap_or_default();
+        ctx.say(format!("📭 {} hasn't taken any quests{} yet.", target.name, when)).await?;
+        return Ok(());
+    }
+
+    let earned: u32 = entries.iter().filter(|e| e.status == "VERIFIED").map(|e| e.points).sum();
+    let pending: u32 = entries.iter().filter(|e| e.status == "COMPLETED").map(|e| e.points).sum();
+
+    let pages: Vec<_> = entries.chunks(5).collect();
+    let embeds = pages.iter().enumerate().map(|(i, chunk)| {
+        let mut embed = serenity::CreateEmbed::new()
+            .title(format!("📜 Quest History: {}{} — Page {} of {}", target.name, season_label(season.as_ref()), i + 1, pages.len()))
+            .description(format!("{} quests · 🏆 {} points earned · ⏳ {} pending review", entries.len(), earned, pending))
+            .color(0x3498DB);
+        for entry in *chunk {
+            let points = match entry.status.as_str() {
+                "VERIFIED" => format!("🏆 {} pts", entry.points),
+                "COMPLETED" => format!("⏳ {} pts pending", entry.points),
+                _ => "—".to_string(),
+            };
+            let proof = match &entry.proof {
+                Some(p) if p.starts_with("http") => format!("[Proof]({})", p),
+                Some(p) => format!("\"{}\"", p.chars().take(80).collect::<String>()),
+                None => "No proof yet".to_string(),
+            };
+            embed = embed.field(
+                format!("{} — `{}`", entry.title, entry.status),
+                format!(
+                    "• ID: `{}`\n• Taken: {} · Submitted: {}\n• {} · {}",
+                    entry.quest_id,
+                    fmt_date(&entry.taken_at),
+                    entry.submitted_at.as_deref().map(fmt_date).unwrap_or_else(|| "-".to_string()),
+                    points,
+                    proof,
+                ),
+                false,
+            );
+        }
+        embed
+    }).collect();
+
+    paginate_embeds(ctx, embeds, true).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::s;
 
     #[test]
     fn test_calculate_stats() {
@@ -132,4 +295,33 @@ mod tests {
         assert!(res.list_str.contains("Quest 1"));
         assert!(!res.list_str.contains("Quest 2"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_build_history() {
+        let q_rows = vec![
+            s(&["id", "title"]),
+            s(&["q1", "Quest 1", "Cat", "5", "Div", "", "", "", "", "", "", "", "", "", "", "3"]),
+            s(&["q2", "Quest 2", "Cat", "5", "Div"]),
+        ];
+        let p_rows = vec![
+            s(&["quest_id", "user_id"]),
+            s(&["q1", "u1", "tag", "VERIFIED", "2025-01-01T10:00:00+07:00"]),
+            s(&["q2", "u1", "tag", "ON_PROGRESS", "2025-02-01T10:00:00+07:00"]),
+            s(&["q1", "u2", "tag", "COMPLETED", "2025-01-01T10:00:00+07:00"]),
+        ];
+        let s_rows = vec![
+            s(&["quest_id", "user_id", "proof", "timestamp"]),
+            s(&["q1", "u1", "https://old", "2025-01-02T00:00:00Z"]),
+            s(&["q1", "u1", "https://new", "2025-01-03T00:00:00Z"]),
+        ];
+
+        let history = build_history("u1", &q_rows, &p_rows, &s_rows);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].quest_id, "q2");
+        assert_eq!(history[0].points, 0);
+        assert_eq!(history[0].proof, None);
+        assert_eq!(history[1].proof.as_deref(), Some("https://new"));
+        assert_eq!(history[1].submitted_at.as_deref(), Some("2025-01-03T00:00:00Z"));
+        assert_eq!(history[1].points, 3 * POINTS_PER_STEP);
+    }
+}
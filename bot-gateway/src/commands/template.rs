@@ -0,0 +1,255 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use common::{normalize_name, slots_from_cell, CronRule};
+use futures_util::{stream, Stream, StreamExt};
+use poise::CreateReply;
+use serenity::all::{AutocompleteChoice, CreateEmbed, CreateEmbedFooter};
+
+use crate::cache::get_cached_sheet_data;
+use crate::kafka::produce_event;
+use crate::models::{TemplatePayload, TemplateSchedulePayload, TemplateStatusPayload};
+use crate::{Data, Error};
+
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+// Templates tab column mapping (0-based), written by the sheet worker:
+// 0: name, 1: title, 2: category, 3: slots, 4: organizer_name, 5: platform,
+// 6: description, 7: duration_minutes, 8: creator_id, 9: rule, 10: lead_hours,
+// 11: status (UNSCHEDULED/ACTIVE/PAUSED), 12: last_occurrence, 13: created_at
+
+async fn autocomplete_template<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = AutocompleteChoice> + 'a {
+    let mut choices = Vec::new();
+
+    if let Ok(data) = get_cached_sheet_data(ctx).await {
+        for row in data.t_rows.iter().skip(1) {
+            let Some(name) = row.first() else { continue };
+            let status = row.get(11).map(|s| s.as_str()).unwrap_or("");
+            let label = format!("{} ({})", name, status);
+            if label.to_lowercase().contains(&partial.to_lowercase()) {
+                choices.push(AutocompleteChoice::new(label, name.clone()));
+            }
+        }
+    }
+
+    stream::iter(choices).take(25)
+}
+
+fn find_template<'a>(t_rows: &'a [Vec<String>], name: &str) -> Option<&'a Vec<String>> {
+    t_rows.iter().skip(1).find(|row| row.first().map(|n| n == name).unwrap_or(false))
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("save", "schedule", "list", "pause", "resume"),
+    description_localized("en-US", "Manage recurring quest templates"),
+    check = "crate::security::check_quest_role"
+)]
+pub async fn template(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Save an existing quest as a template"))]
+pub async fn save(
+    ctx: Context<'_>,
+    #[description = "Quest ID to copy"] quest_id: String,
+    #[description = "Template name (E.g: Mabar MLBB Friday)"] name: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let data = match get_cached_sheet_data(ctx).await {
+        Ok(data) => data,
+        Err(e) => {
+            ctx.say(format!("❌ Failed to query sheet: {}", e)).await?;
+            return Ok(());
+        }
+    };
+
+    let name = name.trim().to_string();
+    let target = normalize_name(&name);
+    if data.t_rows.iter().skip(1).any(|row| row.first().map(|n| normalize_name(n) == target).unwrap_or(false)) {
+        ctx.say(format!("❌ Template `{}` already exists.", name)).await?;
+        return Ok(());
+    }
+
+    let Some(row) = data.q_rows.iter().skip(1).find(|row| row.len() >= 9 && row[0] == quest_id) else {
+        ctx.say(format!("❌ Quest ID `{}` not found.", quest_id)).await?;
+        return Ok(());
+    };
+
+    // column mapping (0-based):
+    // 0: quest_id, 1: title, 2: category, 3: slots, 4: organizer_name,
+    // 5: schedule, 6: platform, 7: description, 8: deadline, 9: created_at
+    let duration_minutes = match (DateTime::parse_from_rfc3339(&row[5]), DateTime::parse_from_rfc3339(&row[8])) {
+        (Ok(start), Ok(end)) => (end - start).num_minutes().max(0),
+        _ => 0,
+    };
+
+    let payload = TemplatePayload {
+        name: name.clone(),
+        title: row[1].clone(),
+        description: row[7].clone(),
+        category: row[2].clone(),
+        organizer_name: row[4].clone(),
+        slots: slots_from_cell(&row[3]),
+        platform: row[6].clone(),
+        duration_minutes,
+        creator_id: ctx.author().id.to_string(),
+    };
+
+    produce_event(ctx, "SAVE_TEMPLATE", &payload).await?;
+
+    ctx.say(format!(
+        "✅ Template **{}** saved from quest **{}** ({} min long). Use `/template schedule` to run it automatically.",
+        name, payload.title, duration_minutes
+    )).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Create quests from a template on a recurring schedule"))]
+pub async fn schedule(
+    ctx: Context<'_>,
+    #[description = "Template"]
+    #[autocomplete = "autocomplete_template"]
+    name: String,
+    #[description = "Start times as cron in WIB: minute hour day month weekday (E.g: 0 19 * * FRI)"] rule: String,
+    #[description = "Hours before the start time to post the quest (default 24)"]
+    #[min = 1]
+    #[max = 720]
+    lead_hours: Option<i64>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let cron = match CronRule::parse(&rule) {
+        Ok(cron) => cron,
+        Err(msg) => {
+            ctx.say(format!("❌ {}", msg)).await?;
+            return Ok(());
+        }
+    };
+
+    let data = get_cached_sheet_data(ctx).await?;
+    if find_template(&data.t_rows, &name).is_none() {
+        ctx.say(format!("❌ Template `{}` not found.", name)).await?;
+        return Ok(());
+    }
+
+    let wib_offset = FixedOffset::east_opt(7 * 3600).unwrap();
+    let Some(next) = cron.next_after(Utc::now().with_timezone(&wib_offset)) else {
+        ctx.say("❌ This rule never matches a date.").await?;
+        return Ok(());
+    };
+
+    let lead_hours = lead_hours.unwrap_or(24);
+    let payload = TemplateSchedulePayload {
+        name: name.clone(),
+        rule: rule.trim().to_string(),
+        lead_hours,
+    };
+
+    produce_event(ctx, "SCHEDULE_TEMPLATE", &payload).await?;
+
+    ctx.say(format!(
+        "✅ Template **{}** scheduled (`{}`). Next quest starts <t:{}:f> and is posted {}h before.",
+        name, payload.rule, next.timestamp(), lead_hours
+    )).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "List quest templates"))]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let wib_offset = FixedOffset::east_opt(7 * 3600).unwrap();
+    let now = Utc::now().with_timezone(&wib_offset);
+
+    let mut embed = CreateEmbed::default()
+        .title("🗂️ Quest Templates")
+        .color(0x3498DB)
+        .footer(CreateEmbedFooter::new("Use /template schedule or /template pause to manage"));
+
+    let mut count = 0;
+    for row in data.t_rows.iter().skip(1).take(25) {
+        let cell = |i: usize| row.get(i).map(|s| s.as_str()).unwrap_or("");
+        if cell(0).is_empty() {
+            continue;
+        }
+
+        let next = match (cell(11), CronRule::parse(cell(9))) {
+            ("ACTIVE", Ok(rule)) => {
+                let last = DateTime::parse_from_rfc3339(cell(12)).unwrap_or(now).max(now);
+                rule.next_after(last)
+                    .map(|dt| format!("<t:{}:f>", dt.timestamp()))
+                    .unwrap_or_else(|| "-".to_string())
+            }
+            _ => "-".to_string(),
+        };
+
+        embed = embed.field(
+            format!("{} — {}", cell(0), cell(11)),
+            format!(
+                "• Quest: {} by {}\n• Rule: `{}`\n• Next start: {}",
+                cell(1),
+                cell(4),
+                if cell(9).is_empty() { "not scheduled" } else { cell(9) },
+                next
+            ),
+            false,
+        );
+        count += 1;
+    }
+
+    if count == 0 {
+        ctx.say("📭 No templates yet. Use `/template save` to create one.").await?;
+        return Ok(());
+    }
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true)).await?;
+    Ok(())
+}
+
+async fn set_status(ctx: Context<'_>, name: String, status: &str) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let data = get_cached_sheet_data(ctx).await?;
+    let Some(row) = find_template(&data.t_rows, &name) else {
+        ctx.say(format!("❌ Template `{}` not found.", name)).await?;
+        return Ok(());
+    };
+
+    if row.get(9).map(|r| r.is_empty()).unwrap_or(true) {
+        ctx.say(format!("❌ Template `{}` has no schedule yet. Use `/template schedule` first.", name)).await?;
+        return Ok(());
+    }
+
+    let payload = TemplateStatusPayload {
+        name: name.clone(),
+        status: status.to_string(),
+    };
+    produce_event(ctx, "SET_TEMPLATE_STATUS", &payload).await?;
+
+    ctx.say(format!("✅ Template **{}** is now `{}`.", name, status)).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Pause a scheduled template"))]
+pub async fn pause(
+    ctx: Context<'_>,
+    #[description = "Template"]
+    #[autocomplete = "autocomplete_template"]
+    name: String,
+) -> Result<(), Error> {
+    set_status(ctx, name, "PAUSED").await
+}
+
+#[poise::command(slash_command, description_localized("en-US", "Resume a paused template"))]
+pub async fn resume(
+    ctx: Context<'_>,
+    #[description = "Template"]
+    #[autocomplete = "autocomplete_template"]
+    name: String,
+) -> Result<(), Error> {
+    set_status(ctx, name, "ACTIVE").await
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use poise::CreateReply;
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::collector::ComponentInteractionCollector;
+
+use crate::{Data, Error};
+
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+#[derive(Debug, poise::Modal)]
+#[name = "Reason"]
+pub struct ReasonModal {
+    #[name = "Reason"]
+    #[paragraph]
+    #[placeholder = "Why is this being done? (shown to affected participants)"]
+    #[max_length = 500]
+    pub reason: String,
+}
+
+/// Shows an ephemeral Confirm / Cancel prompt for a destructive action and,
+/// once confirmed, a modal asking for the reason.
+///
+/// Returns `None` if the user cancels, dismisses the modal, or the prompt times out.
+pub async fn confirm_with_reason(ctx: Context<'_>, prompt: &str) -> Result<Option<String>, Error> {
+    let confirm_id = format!("{}_confirm", ctx.id());
+    let cancel_id = format!("{}_cancel", ctx.id());
+
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(&confirm_id)
+            .label("Confirm")
+            .style(ButtonStyle::Danger),
+        CreateButton::new(&cancel_id)
+            .label("Cancel")
+            .style(ButtonStyle::Secondary),
+    ]);
+
+    let handle = ctx.send(CreateReply::default()
+        .content(prompt)
+        .components(vec![buttons])
+        .ephemeral(true)
+    ).await?;
+
+    let ctx_serenity = ctx.serenity_context();
+    let ids = (confirm_id.clone(), cancel_id.clone());
+    let mut collector = ComponentInteractionCollector::new(ctx_serenity)
+        .author_id(ctx.author().id)
+        .filter(move |i| i.data.custom_id == ids.0 || i.data.custom_id == ids.1)
+        .timeout(Duration::from_secs(60))
+        .stream();
+
+    let Some(interaction) = collector.next().await else {
+        handle.edit(ctx, CreateReply::default()
+            .content("⌛ Confirmation timed out, nothing was changed.")
+            .components(Vec::new())
+        ).await?;
+        return Ok(None);
+    };
+
+    if interaction.data.custom_id == cancel_id {
+        interaction.create_response(&ctx_serenity.http, CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .content("❎ Cancelled, nothing was changed.")
+                .components(Vec::new())
+        )).await?;
+        return Ok(None);
+    }
+
+    let modal = poise::execute_modal_on_component_interaction::<ReasonModal>(
+        ctx,
+        interaction,
+        None,
+        Some(Duration::from_secs(300)),
+    ).await?;
+
+    match modal {
+        Some(data) if !data.reason.trim().is_empty() => {
+            handle.edit(ctx, CreateReply::default()
+                .content("⏳ Confirmed, processing...")
+                .components(Vec::new())
+            ).await?;
+            Ok(Some(data.reason.trim().to_string()))
+        }
+        _ => {
+            handle.edit(ctx, CreateReply::default()
+                .content("❎ No reason given, nothing was changed.")
+                .components(Vec::new())
+            ).await?;
+            Ok(None)
+        }
+    }
+}
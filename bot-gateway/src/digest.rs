@@ -0,0 +1,255 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveTime, Utc};
+use common::{calculate_status, has_free_slot, slots_label, QuestStatus};
+use poise::serenity_prelude as serenity;
+use redis::AsyncCommands;
+use serenity::all::{ChannelId, CreateEmbed, CreateEmbedFooter, CreateMessage};
+
+use crate::cache::{fetch_sheet_data, read_guild_config};
+use crate::commands::list::{collect_quest_summaries, QuestSummary};
+use crate::models::{GuildConfig, NotificationCategory};
+use crate::notify::notify_subscribers;
+use crate::scheduler::JobContext;
+use crate::Error;
+
+const DAY_SECS: i64 = 24 * 3600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestKind {
+    Daily,
+    Weekly,
+}
+
+impl DigestKind {
+    fn window_secs(self) -> i64 {
+        match self {
+            DigestKind::Daily => DAY_SECS,
+            DigestKind::Weekly => 7 * DAY_SECS,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            DigestKind::Daily => "daily",
+            DigestKind::Weekly => "weekly",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            DigestKind::Daily => "📰 Daily Quest Digest",
+            DigestKind::Weekly => "📰 Weekly Quest Digest",
+        }
+    }
+}
+
+/// Quests worth highlighting in a digest covering `window_secs` around `now`.
+#[derive(Debug, Default)]
+pub struct DigestSections<'a> {
+    /// Announced within the last window and not ended yet.
+    pub new_quests: Vec<&'a QuestSummary>,
+    /// Starting within the next window and still having open slots.
+    pub starting_soon: Vec<&'a QuestSummary>,
+    /// Still running or upcoming, with a deadline within the next window.
+    pub closing_soon: Vec<&'a QuestSummary>,
+}
+
+impl DigestSections<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.new_quests.is_empty() && self.starting_soon.is_empty() && self.closing_soon.is_empty()
+    }
+}
+
+pub fn build_digest(quests: &[QuestSummary], now: i64, window_secs: i64) -> DigestSections<'_> {
+    let mut sections = DigestSections::default();
+
+    for quest in quests {
+        let status = calculate_status(now, &quest.schedule_ts, &quest.deadline_ts);
+        if status == QuestStatus::Ended {
+            continue;
+        }
+
+        if quest.created_ts > now - window_secs && quest.created_ts <= now {
+            sections.new_quests.push(quest);
+        }
+        if quest.schedule_ts > now && quest.schedule_ts <= now + window_secs && has_free_slot(quest.filled, quest.max_slots) {
+            sections.starting_soon.push(quest);
+        }
+        if quest.deadline_ts > now && quest.deadline_ts <= now + window_secs {
+            sections.closing_soon.push(quest);
+        }
+    }
+
+    sections.starting_soon.sort_by_key(|q| q.schedule_ts);
+    sections.closing_soon.sort_by_key(|q| q.deadline_ts);
+    sections
+}
+
+pub fn render_digest(sections: &DigestSections<'_>) -> String {
+    let mut out = String::new();
+
+    let mut push_section = |heading: &str, quests: &[&QuestSummary], line: &dyn Fn(&QuestSummary) -> String| {
+        if quests.is_empty() {
+            return;
+        }
+        out.push_str(&format!("**{}**\n", heading));
+        for quest in quests {
+            out.push_str(&line(quest));
+            out.push('\n');
+        }
+        out.push('\n');
+    };
+
+    push_section("🆕 New Quests", &sections.new_quests, &|q| {
+        format!("• **{}** by {} @ {} — `{}`", q.title, q.organizer, q.platform, q.quest_id)
+    });
+    push_section("🟢 Starting Soon (slots open)", &sections.starting_soon, &|q| {
+        format!("• **{}** <t:{}:R> — {}/{} filled — `{}`", q.title, q.schedule_ts, q.filled, slots_label(q.max_slots), q.quest_id)
+    });
+    push_section("⏰ Closing Soon", &sections.closing_soon, &|q| {
+        format!("• **{}** closes <t:{}:R> — `{}`", q.title, q.deadline_ts, q.quest_id)
+    });
+
+    out.trim_end().to_string()
+}
+
+fn truncate(mut text: String, max: usize) -> String {
+    if text.len() > max {
+        let mut cut = max - 10;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        text.truncate(cut);
+        text.push_str("\n...(etc)");
+    }
+    text
+}
+
+/// Whether a digest configured for `time` ("HH:MM") should go out at `now_wib`,
+/// given the WIB date it was last sent on.
+pub fn digest_due(now_wib: DateTime<FixedOffset>, time: &str, last_sent: Option<&str>) -> bool {
+    let Ok(at) = NaiveTime::parse_from_str(time, "%H:%M") else {
+        return false;
+    };
+    let today = now_wib.format("%Y-%m-%d").to_string();
+    now_wib.time() >= at && last_sent != Some(today.as_str())
+}
+
+async fn post_digest(job: &JobContext, config: &GuildConfig, kind: DigestKind) -> Result<(), Error> {
+    let data = fetch_sheet_data(&job.redis_client, &job.sheets_hub, &job.google_sheet_id).await?;
+    let quests = collect_quest_summaries(&data.q_rows, &data.p_rows);
+    let now = Utc::now().timestamp();
+
+    let sections = build_digest(&quests, now, kind.window_secs());
+    if sections.is_empty() {
+        println!("Skipping {} digest: nothing to report", kind.key());
+        return Ok(());
+    }
+    let body = render_digest(&sections);
+
+    if let Some(channel_id) = config.announcement_channel_id {
+        let embed = CreateEmbed::default()
+            .title(kind.title())
+            .description(truncate(body.clone(), 4000))
+            .color(0x3498DB)
+            .footer(CreateEmbedFooter::new("Use /take <id> to take a quest"));
+        ChannelId::new(channel_id)
+            .send_message(&job.http, CreateMessage::new().embed(embed))
+            .await?;
+    }
+
+    notify_subscribers(
+        &job.http,
+        &job.redis_client,
+        config,
+        NotificationCategory::Digests,
+        &truncate(format!("{}\n\n{}", kind.title(), body), 1900),
+    ).await;
+
+    Ok(())
+}
+
+/// Posts the daily and/or weekly digest if their configured time has passed today
+/// and they haven't been sent yet.
+pub async fn post_due_digests(job: &JobContext) -> Result<(), Error> {
+    let config = read_guild_config(&job.redis_client, job.guild_id.get()).await?;
+    let Some(time) = config.digest_time.clone() else {
+        return Ok(());
+    };
+
+    let wib_offset = FixedOffset::east_opt(7 * 3600).unwrap();
+    let now_wib = Utc::now().with_timezone(&wib_offset);
+    let today = now_wib.format("%Y-%m-%d").to_string();
+
+    let mut due = Vec::new();
+    if config.daily_digest {
+        due.push(DigestKind::Daily);
+    }
+    if config.weekly_digest_day.map(|d| d.to_chrono()) == Some(now_wib.weekday()) {
+        due.push(DigestKind::Weekly);
+    }
+
+    let mut con = job.redis_client.get_multiplexed_async_connection().await?;
+    for kind in due {
+        let last_key = format!("digest_last:{}:{}", kind.key(), job.guild_id);
+        let last_sent: Option<String> = con.get(&last_key).await.ok();
+        if !digest_due(now_wib, &time, last_sent.as_deref()) {
+            continue;
+        }
+
+        // Mark first so a failing post doesn't get retried every minute.
+        let _: () = con.set(&last_key, &today).await?;
+        post_digest(job, &config, kind).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quest(id: &str, created: i64, start: i64, end: i64, filled: u32, max: u32) -> QuestSummary {
+        QuestSummary {
+            quest_id: id.to_string(),
+            title: id.to_string(),
+            description: String::new(),
+            category: "CreativeArts".to_string(),
+            organizer: "Illust".to_string(),
+            platform: "Discord".to_string(),
+            schedule_ts: start,
+            deadline_ts: end,
+            created_ts: created,
+            max_slots: Some(max),
+            filled,
+        }
+    }
+
+    #[test]
+    fn test_build_digest_sections() {
+        let now = 100 * DAY_SECS;
+        let quests = vec![
+            quest("new", now - 3600, now + 3 * DAY_SECS, now + 4 * DAY_SECS, 0, 5),
+            quest("soon", now - 5 * DAY_SECS, now + 3600, now + 7200, 1, 5),
+            quest("soon_full", now - 5 * DAY_SECS, now + 3600, now + 7200, 5, 5),
+            quest("ended", now - 3600, now - 7200, now - 3600, 0, 5),
+        ];
+
+        let sections = build_digest(&quests, now, DAY_SECS);
+        let ids = |v: &[&QuestSummary]| v.iter().map(|q| q.quest_id.clone()).collect::<Vec<_>>();
+
+        assert_eq!(ids(&sections.new_quests), vec!["new"]);
+        assert_eq!(ids(&sections.starting_soon), vec!["soon"]);
+        assert_eq!(ids(&sections.closing_soon), vec!["soon", "soon_full"]);
+    }
+
+    #[test]
+    fn test_digest_due() {
+        let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+        let now = DateTime::parse_from_rfc3339("2025-11-20T08:30:00+07:00").unwrap().with_timezone(&wib);
+
+        assert!(digest_due(now, "08:00", None));
+        assert!(digest_due(now, "08:00", Some("2025-11-19")));
+        assert!(!digest_due(now, "08:00", Some("2025-11-20")));
+        assert!(!digest_due(now, "09:00", None));
+        assert!(!digest_due(now, "not a time", None));
+    }
+}
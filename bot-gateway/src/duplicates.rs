@@ -0,0 +1,150 @@
+use image::imageops::FilterType;
+use image::DynamicImage;
+use redis::{AsyncCommands, Client as RedisClient};
+
+use crate::models::ProofRecord;
+use crate::Error;
+
+/// Images whose perceptual hashes differ in at most this many bits are treated as the same picture.
+const MAX_PHASH_DISTANCE: u32 = 6;
+
+/// Size requested from Discord's media proxy before hashing, so only a small
+/// thumbnail is downloaded and decoded.
+const PROXY_THUMB_SIZE: u32 = 64;
+
+/// Hashes identifying a submitted proof file.
+#[derive(Debug, Clone)]
+pub struct ProofFingerprint {
+    pub sha256: String,
+    /// Difference hash of the image, `None` for non-image proofs or when the
+    /// thumbnail couldn't be fetched.
+    pub phash: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub enum DuplicateMatch {
+    /// Byte-for-byte the same file.
+    Exact(ProofRecord),
+    /// A visually near-identical image (resized, recompressed, ...).
+    Similar(ProofRecord),
+}
+
+/// 64-bit difference hash: shrink to 9x8 grayscale and record whether each pixel
+/// is brighter than its right-hand neighbour.
+fn dhash(image: &DynamicImage) -> u64 {
+    let small = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+/// Perceptual hash of an image attachment, fetched as a small thumbnail through
+/// Discord's media proxy.
+pub async fn perceptual_hash(proxy_url: &str) -> Option<u64> {
+    let separator = if proxy_url.contains('?') { '&' } else { '?' };
+    let url = format!("{}{}width={}&height={}", proxy_url, separator, PROXY_THUMB_SIZE, PROXY_THUMB_SIZE);
+    let bytes = match reqwest::get(&url).await {
+        Ok(response) if response.status().is_success() => response.bytes().await.ok()?,
+        Ok(response) => {
+            eprintln!("Thumbnail fetch for perceptual hash failed with {}", response.status());
+            return None;
+        }
+        Err(e) => {
+            eprintln!("Thumbnail fetch for perceptual hash failed: {:?}", e);
+            return None;
+        }
+    };
+    match image::load_from_memory(&bytes) {
+        Ok(image) => Some(dhash(&image)),
+        Err(e) => {
+            eprintln!("Couldn't decode thumbnail for perceptual hash: {:?}", e);
+            None
+        }
+    }
+}
+
+/// Looks for an earlier submission of the same file, ignoring the user's own
+/// earlier proofs for this quest (e.g. re-sending a step).
+pub async fn find_duplicate(
+    redis_client: &RedisClient,
+    fingerprint: &ProofFingerprint,
+    quest_id: &str,
+    user_id: &str,
+) -> Result<Option<DuplicateMatch>, Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let is_other = |r: &ProofRecord| !(r.quest_id == quest_id && r.user_id == user_id);
+
+    let exact: Option<String> = con.hget("proof_hashes", &fingerprint.sha256).await?;
+    if let Some(record) = exact.and_then(|j| serde_json::from_str::<ProofRecord>(&j).ok()) {
+        if is_other(&record) {
+            return Ok(Some(DuplicateMatch::Exact(record)));
+        }
+    }
+
+    let Some(phash) = fingerprint.phash else {
+        return Ok(None);
+    };
+    let known: Vec<(String, String)> = con.hgetall("proof_phashes").await?;
+    let similar = known.into_iter()
+        .filter_map(|(hash, json)| {
+            let distance = (u64::from_str_radix(&hash, 16).ok()? ^ phash).count_ones();
+            let record = serde_json::from_str::<ProofRecord>(&json).ok()?;
+            (distance <= MAX_PHASH_DISTANCE && is_other(&record)).then_some((distance, record))
+        })
+        .min_by_key(|(distance, _)| *distance);
+
+    Ok(similar.map(|(_, record)| DuplicateMatch::Similar(record)))
+}
+
+/// Remembers where a file was submitted. The first submission of a file is kept.
+pub async fn record_proof(redis_client: &RedisClient, fingerprint: &ProofFingerprint, record: &ProofRecord) -> Result<(), Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let json = serde_json::to_string(record)?;
+    let _: bool = con.hset_nx("proof_hashes", &fingerprint.sha256, &json).await?;
+    if let Some(phash) = fingerprint.phash {
+        let _: bool = con.hset_nx("proof_phashes", format!("{:016x}", phash), &json).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::GrayImage;
+
+    fn gradient(rising: bool) -> DynamicImage {
+        DynamicImage::ImageLuma8(GrayImage::from_fn(64, 64, |x, _| {
+            let v = (x * 4) as u8;
+            image::Luma([if rising { v } else { 255 - v }])
+        }))
+    }
+
+    #[test]
+    fn test_dhash_gradients() {
+        assert_eq!(dhash(&gradient(true)), 0);
+        assert_eq!(dhash(&gradient(false)), u64::MAX);
+
+        // Slight brightness change keeps the hash.
+        let mut brighter = gradient(false).to_luma8();
+        brighter.pixels_mut().for_each(|p| p[0] = p[0].saturating_add(3));
+        let distance = (dhash(&DynamicImage::ImageLuma8(brighter)) ^ dhash(&gradient(false))).count_ones();
+        assert!(distance <= MAX_PHASH_DISTANCE);
+    }
+
+    #[test]
+    fn test_dhash_survives_reencoding() {
+        let mut png = std::io::Cursor::new(Vec::new());
+        gradient(false).write_to(&mut png, image::ImageFormat::Png).unwrap();
+        let decoded = image::load_from_memory(png.get_ref()).unwrap();
+        assert_eq!(dhash(&decoded), u64::MAX);
+
+        assert!(image::load_from_memory(b"not an image").is_err());
+    }
+}
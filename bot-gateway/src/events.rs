@@ -0,0 +1,26 @@
+use poise::serenity_prelude as serenity;
+use serenity::all::{FullEvent, Interaction};
+
+use crate::commands::checkin::{handle_checkin_button, CHECKIN_BUTTON_PREFIX};
+use crate::voice::{handle_voice_update, reset_voice_sessions, VoiceTracker};
+use crate::{Data, Error};
+
+/// Gateway events handled outside of commands: components that outlive the
+/// command that posted them (e.g. Check In buttons) and voice presence.
+pub async fn handle_event(ctx: &serenity::Context, event: &FullEvent, data: &Data) -> Result<(), Error> {
+    match event {
+        FullEvent::InteractionCreate { interaction: Interaction::Component(component) }
+            if component.data.custom_id.starts_with(CHECKIN_BUTTON_PREFIX) =>
+        {
+            handle_checkin_button(ctx, component, data).await?;
+        }
+        FullEvent::VoiceStateUpdate { new, .. } => {
+            handle_voice_update(&VoiceTracker::from_data(&ctx.http, data), new).await?;
+        }
+        FullEvent::GuildCreate { guild, .. } if guild.id == data.target_guild_id => {
+            reset_voice_sessions(&data.redis_client, guild).await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use std::collections::HashSet;
+use std::io::{Cursor, Write};
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, TimeZone, Timelike, Utc};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::cache::{fetch_sheet_data, fetch_submission_rows};
+use crate::commands::season::quest_in_season;
+use crate::models::Season;
+use crate::{Error, HubType};
+
+/// Quotes a CSV field when it contains a separator, quote or line break. Fields that
+/// a spreadsheet would read as a formula (`=`, `+`, `-`, `@`) get a leading `'`.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Renders rows (header first) as CSV with CRLF line endings.
+pub fn to_csv(rows: &[Vec<String>]) -> String {
+    rows.iter()
+        .map(|row| row.iter().map(|v| csv_field(v)).collect::<Vec<_>>().join(","))
+        .map(|line| line + "\r\n")
+        .collect()
+}
+
+/// Parses CSV text into records, each with the 1-based line it starts on.
+/// Handles quoted fields (with `""` escapes and line breaks) and CRLF or LF
+/// line endings. Blank lines are skipped.
+pub fn parse_csv(text: &str) -> Vec<(usize, Vec<String>)> {
+    let mut records = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let (mut line, mut record_line) = (1, 1);
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                if row.iter().any(|f| !f.is_empty()) {
+                    records.push((record_line, std::mem::take(&mut row)));
+                }
+                row.clear();
+                line += 1;
+                record_line = line;
+            }
+            ('\n', true) => {
+                field.push(c);
+                line += 1;
+            }
+            _ => field.push(c),
+        }
+    }
+    row.push(field);
+    if row.iter().any(|f| !f.is_empty()) {
+        records.push((record_line, row));
+    }
+    records
+}
+
+/// Which quests go into an export; participants and submissions follow their quest.
+#[derive(Debug, Clone, Default)]
+pub struct ExportFilter {
+    /// Keep quests starting at or after this time (unix seconds).
+    pub from: Option<i64>,
+    /// Keep quests starting at or before this time (unix seconds).
+    pub to: Option<i64>,
+    /// Keep quests organized by this division (or community), case-insensitive.
+    pub division: Option<String>,
+    /// Keep quests of this season.
+    pub season: Option<Season>,
+}
+
+impl ExportFilter {
+    /// Builds a filter from `YYYY-MM-DD` dates (whole days, WIB) and an organizer name.
+    pub fn parse(from: Option<&str>, to: Option<&str>, division: Option<&str>) -> Result<ExportFilter, String> {
+        let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+        let day = |value: &str, label: &str| NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("Wrong {} date! Use: YYYY-MM-DD (E.g: 2025-11-25)", label));
+
+        let from = from.filter(|v| !v.trim().is_empty())
+            .map(|v| day(v, "start").map(|d| wib.from_local_datetime(&d.and_hms_opt(0, 0, 0).unwrap()).unwrap().timestamp()))
+            .transpose()?;
+        let to = to.filter(|v| !v.trim().is_empty())
+            .map(|v| day(v, "end").map(|d| wib.from_local_datetime(&d.and_hms_opt(23, 59, 59).unwrap()).unwrap().timestamp()))
+            .transpose()?;
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                return Err("The start date is after the end date.".to_string());
+            }
+        }
+        let division = division.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+        Ok(ExportFilter { from, to, division, season: None })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.from.is_none() && self.to.is_none() && self.division.is_none() && self.season.is_none()
+    }
+
+    /// Checks a Quests row. Quests without a start time only pass when no date is set.
+    fn matches(&self, row: &[String]) -> bool {
+        // column mapping (0-based): 4: organizer_name, 5: schedule
+        if let Some(division) = &self.division {
+            if !row.get(4).is_some_and(|o| o.eq_ignore_ascii_case(division)) {
+                return false;
+            }
+        }
+        if self.season.as_ref().is_some_and(|season| !quest_in_season(row, season)) {
+            return false;
+        }
+        if self.from.is_none() && self.to.is_none() {
+            return true;
+        }
+        let Some(start) = row.get(5).and_then(|s| DateTime::parse_from_rfc3339(s).ok()).map(|dt| dt.timestamp()) else {
+            return false;
+        };
+        self.from.is_none_or(|from| start >= from) && self.to.is_none_or(|to| start <= to)
+    }
+}
+
+/// Turns a tab (header row first) into a JSON array of objects keyed by the header.
+/// Columns without a header are named after their letter.
+fn rows_to_json(rows: &[Vec<String>]) -> serde_json::Value {
+    let header = rows.first().cloned().unwrap_or_default();
+    let name = |i: usize| match header.get(i).filter(|h| !h.trim().is_empty()) {
+        Some(h) => h.trim().to_string(),
+        None => ((b'A' + (i % 26) as u8) as char).to_string(),
+    };
+    let objects = rows.iter().skip(1)
+        .map(|row| {
+            let object: serde_json::Map<String, serde_json::Value> = row.iter().enumerate()
+                .map(|(i, cell)| (name(i), serde_json::Value::String(cell.clone())))
+                .collect();
+            serde_json::Value::Object(object)
+        })
+        .collect();
+    serde_json::Value::Array(objects)
+}
+
+/// Keeps the header row and the rows whose first column (quest id) is in `quest_ids`.
+fn rows_for_quests(rows: &[Vec<String>], quest_ids: &HashSet<&str>) -> Vec<Vec<String>> {
+    rows.iter().take(1)
+        .chain(rows.iter().skip(1).filter(|row| row.first().is_some_and(|id| quest_ids.contains(id.as_str()))))
+        .cloned()
+        .collect()
+}
+
+/// Builds the export files (`<tab>.csv` and `<tab>.json`) from raw sheet rows.
+pub fn export_files(
+    q_rows: &[Vec<String>],
+    p_rows: &[Vec<String>],
+    s_rows: &[Vec<String>],
+    c_rows: &[Vec<String>],
+    filter: &ExportFilter,
+) -> Result<Vec<(String, Vec<u8>)>, Error> {
+    let quests: Vec<Vec<String>> = q_rows.iter().take(1)
+        .chain(q_rows.iter().skip(1).filter(|row| filter.matches(row)))
+        .cloned()
+        .collect();
+    let quest_ids: HashSet<&str> = quests.iter().skip(1).filter_map(|row| row.first().map(|id| id.as_str())).collect();
+    let (participants, submissions) = if filter.is_empty() {
+        (p_rows.to_vec(), s_rows.to_vec())
+    } else {
+        (rows_for_quests(p_rows, &quest_ids), rows_for_quests(s_rows, &quest_ids))
+    };
+
+    let mut files = Vec::new();
+    let tabs = [("quests", quests.as_slice()), ("participants", &participants), ("submissions", &submissions), ("communities", c_rows)];
+    for (name, rows) in tabs {
+        files.push((format!("{}.csv", name), to_csv(rows).into_bytes()));
+        files.push((format!("{}.json", name), serde_json::to_vec_pretty(&rows_to_json(rows))?));
+    }
+    Ok(files)
+}
+
+/// Packs files into a deflate-compressed zip archive.
+pub fn zip(files: &[(String, Vec<u8>)], modified: DateTime<Utc>) -> Result<Vec<u8>, Error> {
+    let modified = zip::DateTime::from_date_and_time(
+        modified.year() as u16, modified.month() as u8, modified.day() as u8,
+        modified.hour() as u8, modified.minute() as u8, modified.second() as u8,
+    ).unwrap_or_default();
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(modified);
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in files {
+        writer.start_file(name.as_str(), options)?;
+        writer.write_all(contents)?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+/// Reads the sheet through the same cache as the commands (plus the Submissions tab)
+/// and returns the zipped export. Shared by `/export` and `GET /api/export`.
+pub async fn build_export(
+    redis_client: &redis::Client,
+    hub: &HubType,
+    sheet_id: &str,
+    filter: &ExportFilter,
+) -> Result<Vec<u8>, Error> {
+    let data = fetch_sheet_data(redis_client, hub, sheet_id).await?;
+    let s_rows = fetch_submission_rows(hub, sheet_id).await?;
+    let files = export_files(&data.q_rows, &data.p_rows, &s_rows, &data.c_rows, filter)?;
+    zip(&files, Utc::now())
+}
+
+/// File name for an export taken now, e.g. `gst-export-20251125-1930.zip` (WIB).
+pub fn export_filename() -> String {
+    let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+    format!("gst-export-{}.zip", Utc::now().with_timezone(&wib).format("%Y%m%d-%H%M"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::s;
+
+    #[test]
+    fn test_to_csv_quotes_special_fields() {
+        let rows = vec![
+            vec!["id".to_string(), "title".to_string()],
+            vec!["q1".to_string(), "Draw, paint".to_string()],
+            vec!["q2".to_string(), "Say \"hi\"\nthen go".to_string()],
+        ];
+        assert_eq!(to_csv(&rows), "id,title\r\nq1,\"Draw, paint\"\r\nq2,\"Say \"\"hi\"\"\nthen go\"\r\n");
+        let parsed: Vec<Vec<String>> = parse_csv(&to_csv(&rows)).into_iter().map(|(_, row)| row).collect();
+        assert_eq!(parsed, rows);
+    }
+
+    #[test]
+    fn test_parse_csv_line_endings_and_blanks() {
+        let records = parse_csv("a,b\r\n\r\n1,\"x\ny\"\n2,\n");
+        assert_eq!(records, vec![
+            (1, vec!["a".to_string(), "b".to_string()]),
+            (3, vec!["1".to_string(), "x\ny".to_string()]),
+            (5, vec!["2".to_string(), "".to_string()]),
+        ]);
+    }
+
+    #[test]
+    fn test_export_filter() {
+        let filter = ExportFilter::parse(Some("2025-01-01"), Some("2025-01-31"), Some("illust")).unwrap();
+        let q_rows = vec![
+            s(&["quest_id", "title", "category", "slots", "organizer", "schedule"]),
+            s(&["q1", "In range", "CreativeArts", "5", "Illust", "2025-01-31T23:00:00+07:00"]),
+            s(&["q2", "Too late", "CreativeArts", "5", "Illust", "2025-02-01T00:00:00+07:00"]),
+            s(&["q3", "Other division", "CreativeArts", "5", "Music", "2025-01-10T10:00:00+07:00"]),
+        ];
+        let p_rows = vec![s(&["quest_id", "user_id"]), s(&["q1", "u1"]), s(&["q2", "u2"])];
+        let files = export_files(&q_rows, &p_rows, &[], &[], &filter).unwrap();
+        assert_eq!(files.len(), 8);
+
+        let quests = String::from_utf8(files[0].1.clone()).unwrap();
+        assert!(quests.contains("q1,In range") && !quests.contains("q2") && !quests.contains("q3"));
+        let participants: serde_json::Value = serde_json::from_slice(&files[3].1).unwrap();
+        assert_eq!(participants, serde_json::json!([{ "quest_id": "q1", "user_id": "u1" }]));
+
+        let season = Season { id: "GST25".into(), start: "2025-01-15T00:00:00+07:00".into(), end: None };
+        let filter = ExportFilter { season: Some(season), ..ExportFilter::default() };
+        let q_rows = vec![q_rows[0].clone(), s(&["q1", "Old", "Community", "5", "GST", "", "", "", "", "2025-01-01T00:00:00+07:00"]),
+                          s(&["q2", "New", "Community", "5", "GST", "", "", "", "", "2025-01-20T00:00:00+07:00"])];
+        let quests = String::from_utf8(export_files(&q_rows, &p_rows, &[], &[], &filter).unwrap()[0].1.clone()).unwrap();
+        assert!(quests.contains("q2,New") && !quests.contains("q1"));
+
+        assert!(ExportFilter::parse(Some("2025-02-01"), Some("2025-01-01"), None).is_err());
+        assert!(ExportFilter::parse(Some("01/02/2025"), None, None).is_err());
+    }
+
+    #[test]
+    fn test_to_csv_guards_formulas() {
+        let rows = vec![s(&["=HYPERLINK(\"x\")", "+1", "-", "@me", "a=b"])];
+        assert_eq!(to_csv(&rows), "\"'=HYPERLINK(\"\"x\"\")\",'+1,'-,'@me,a=b\r\n");
+    }
+
+    #[test]
+    fn test_zip_round_trip() {
+        let files = vec![
+            ("a.txt".to_string(), b"hello hello hello".to_vec()),
+            ("b.json".to_string(), b"[]".to_vec()),
+        ];
+        let archive = zip(&files, Utc.with_ymd_and_hms(2025, 11, 25, 19, 30, 0).unwrap()).unwrap();
+
+        let mut reader = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+        assert_eq!(reader.len(), 2);
+        let mut entry = reader.by_name("a.txt").unwrap();
+        assert_eq!(entry.compression(), CompressionMethod::Deflated);
+        let mut decoded = Vec::new();
+        std::io::Read::read_to_end(&mut entry, &mut decoded).unwrap();
+        assert_eq!(decoded, b"hello hello hello");
+    }
+}
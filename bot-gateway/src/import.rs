@@ -0,0 +1,248 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+
+use common::{parse_slots, parse_wib};
+use poise::ChoiceParameter;
+
+use crate::commands::quest::determine_organizer;
+use crate::export::parse_csv;
+use crate::models::{Division, ProofKind, QuestCategory, QuestPayload};
+
+/// Most quests accepted from one file.
+pub const MAX_IMPORT_ROWS: usize = 100;
+
+const REQUIRED_COLUMNS: [&str; 5] = ["title", "category", "slots", "schedule", "platform"];
+const OPTIONAL_COLUMNS: [&str; 8] = [
+    "division", "community", "deadline", "description", "team_size", "steps", "proof_types", "prerequisites",
+];
+
+/// One quest read from an import file: its position (for error messages) and raw fields.
+#[derive(Debug, Clone)]
+pub struct ImportRecord {
+    /// "line 3" for CSV, "item 3" for JSON.
+    pub label: String,
+    pub fields: HashMap<String, String>,
+}
+
+/// Matches a choice by variant name (`CreativeArts`) or display name (`Creative Arts`).
+fn parse_choice<T: ChoiceParameter + Debug>(value: &str) -> Option<T> {
+    let value = value.trim();
+    (0..).map_while(T::from_index)
+        .find(|choice| format!("{:?}", choice).eq_ignore_ascii_case(value) || choice.name().eq_ignore_ascii_case(value))
+}
+
+fn check_columns<'a>(columns: impl Iterator<Item = &'a str>) -> Result<(), String> {
+    let columns: HashSet<&str> = columns.collect();
+    let unknown: Vec<&str> = columns.iter()
+        .filter(|c| !REQUIRED_COLUMNS.contains(c) && !OPTIONAL_COLUMNS.contains(c))
+        .copied()
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!("Unknown column(s): {}", unknown.join(", ")));
+    }
+    let missing: Vec<&str> = REQUIRED_COLUMNS.iter().filter(|c| !columns.contains(*c)).copied().collect();
+    if !missing.is_empty() {
+        return Err(format!("Missing column(s): {}", missing.join(", ")));
+    }
+    Ok(())
+}
+
+/// Reads a CSV (header row first) or JSON (array of objects) import file.
+/// Errors are about the whole file; per-quest problems come from [`validate_record`].
+pub fn parse_import(filename: &str, text: &str) -> Result<Vec<ImportRecord>, String> {
+    let records = if filename.to_lowercase().ends_with(".json") {
+        let items: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_str(text)
+            .map_err(|e| format!("Invalid JSON (expected an array of objects): {}", e))?;
+        let mut records = Vec::new();
+        for (index, item) in items.into_iter().enumerate() {
+            let fields: HashMap<String, String> = item.into_iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::String(s) => s,
+                        serde_json::Value::Null => String::new(),
+                        serde_json::Value::Array(values) => values.iter()
+                            .map(|v| v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string()))
+                            .collect::<Vec<_>>()
+                            .join(","),
+                        other => other.to_string(),
+                    };
+                    (key.trim().to_lowercase(), value)
+                })
+                .collect();
+            check_columns(fields.keys().map(|k| k.as_str())).map_err(|e| format!("item {}: {}", index + 1, e))?;
+            records.push(ImportRecord { label: format!("item {}", index + 1), fields });
+        }
+        records
+    } else {
+        let mut rows = parse_csv(text).into_iter();
+        let Some((_, header)) = rows.next() else {
+            return Err("The file is empty.".to_string());
+        };
+        let header: Vec<String> = header.iter().map(|h| h.trim().to_lowercase()).collect();
+        check_columns(header.iter().map(|h| h.as_str()))?;
+        rows.map(|(line, row)| ImportRecord {
+            label: format!("line {}", line),
+            fields: header.iter().cloned().zip(row.into_iter().chain(std::iter::repeat(String::new()))).collect(),
+        })
+        .collect()
+    };
+
+    if records.is_empty() {
+        return Err("No quests found in the file.".to_string());
+    }
+    if records.len() > MAX_IMPORT_ROWS {
+        return Err(format!("Too many quests ({}); import at most {} at a time.", records.len(), MAX_IMPORT_ROWS));
+    }
+    Ok(records)
+}
+
+/// Checks one record the same way `/create` checks its options and modal, collecting
+/// every problem. The returned payload has no quest id, creator or status yet.
+pub fn validate_record(record: &ImportRecord, known_quest_ids: &HashSet<&str>) -> Result<QuestPayload, Vec<String>> {
+    let field = |name: &str| record.fields.get(name).map(|v| v.trim()).unwrap_or("");
+    let mut errors = Vec::new();
+
+    let title = field("title");
+    if title.is_empty() {
+        errors.push("title is empty".to_string());
+    }
+
+    let category = parse_choice::<QuestCategory>(field("category"));
+    if category.is_none() {
+        errors.push(format!("unknown category `{}` (Creative Arts or Community)", field("category")));
+    }
+    let division = if field("division").is_empty() {
+        Some(Division::None)
+    } else {
+        parse_choice::<Division>(field("division"))
+    };
+    if division.is_none() {
+        errors.push(format!("unknown division `{}`", field("division")));
+    }
+    let mut organizer_name = String::new();
+    if let (Some(category), Some(division)) = (category, division) {
+        let community = Some(field("community").to_string()).filter(|c| !c.is_empty());
+        match determine_organizer(category, division, community) {
+            Ok(organizer) => organizer_name = organizer,
+            Err(e) => errors.push(e.trim_start_matches("Error: ").to_string()),
+        }
+    }
+
+    let slots = parse_slots(field("slots")).map_err(|e| errors.push(format!("slots: {}", e))).ok();
+
+    let schedule = parse_wib(field("schedule")).map_err(|e| errors.push(format!("schedule: {}", e))).ok();
+    let deadline = if field("deadline").is_empty() {
+        schedule.clone()
+    } else {
+        parse_wib(field("deadline")).map_err(|e| errors.push(format!("deadline: {}", e))).ok()
+    };
+
+    let platform = field("platform");
+    if platform.is_empty() {
+        errors.push("platform/location is empty".to_string());
+    }
+
+    let mut optional_number = |name: &str, range: std::ops::RangeInclusive<i8>| -> i8 {
+        let value = field(name);
+        if value.is_empty() {
+            return 0;
+        }
+        match value.parse::<i8>() {
+            Ok(n) if range.contains(&n) => n,
+            _ => {
+                errors.push(format!("{} must be from {} to {} (got `{}`)", name, range.start(), range.end(), value));
+                0
+            }
+        }
+    };
+    let team_size = optional_number("team_size", 2..=25);
+    let steps = optional_number("steps", 2..=10);
+
+    let mut proof_kinds = Vec::new();
+    for name in field("proof_types").split(',').filter(|n| !n.trim().is_empty()) {
+        match ProofKind::parse(name) {
+            Some(kind) if !proof_kinds.contains(&kind) => proof_kinds.push(kind),
+            Some(_) => {}
+            None => errors.push(format!("unknown proof type `{}`", name.trim())),
+        }
+    }
+
+    let prerequisites: Vec<String> = field("prerequisites").split(',')
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+    for id in &prerequisites {
+        if !known_quest_ids.contains(id.as_str()) {
+            errors.push(format!("unknown prerequisite quest ID `{}`", id));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(QuestPayload {
+        quest_id: String::new(),
+        title: title.to_string(),
+        description: field("description").to_string(),
+        category: format!("{:?}", category.unwrap()),
+        organizer_name,
+        slots: slots.unwrap(),
+        schedule: schedule.unwrap(),
+        platform: platform.to_string(),
+        deadline: deadline.unwrap(),
+        creator_id: String::new(),
+        cloned_from: None,
+        status: String::new(),
+        team_size,
+        prerequisites,
+        steps,
+        proof_kinds,
+        season: String::new(),
+        publish_at: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_validate_csv() {
+        let csv = "title,category,division,community,slots,schedule,platform,steps\n\
+                   Sketch Jam,Creative Arts,Illustration,,5,2030-01-10 19:00,Discord,\n\
+                   Meetup,community,,,0,2030-13-01 19:00,,1\n";
+        let records = parse_import("quests.csv", csv).unwrap();
+        assert_eq!(records.len(), 2);
+        let known = HashSet::new();
+
+        let payload = validate_record(&records[0], &known).unwrap();
+        assert_eq!(payload.category, "CreativeArts");
+        assert_eq!(payload.organizer_name, "Illust");
+        assert_eq!(payload.slots, Some(5));
+        assert_eq!(payload.deadline, payload.schedule);
+
+        assert_eq!(records[1].label, "line 3");
+        let errors = validate_record(&records[1], &known).unwrap_err();
+        assert_eq!(errors.len(), 5, "{:?}", errors);
+        assert!(errors.iter().any(|e| e.contains("Community Name")));
+        assert!(errors.iter().any(|e| e.starts_with("slots")));
+        assert!(errors.iter().any(|e| e.starts_with("schedule")));
+        assert!(errors.iter().any(|e| e.starts_with("platform")));
+        assert!(errors.iter().any(|e| e.starts_with("steps")));
+    }
+
+    #[test]
+    fn test_parse_json_and_bad_columns() {
+        let json = r#"[{"title": "Cosplay Walk", "category": "Community", "community": "GST", "slots": 12,
+                        "schedule": "2030-02-01 10:00", "platform": "Mall", "proof_types": ["image", "link"]}]"#;
+        let records = parse_import("quests.json", json).unwrap();
+        let payload = validate_record(&records[0], &HashSet::new()).unwrap();
+        assert_eq!(payload.organizer_name, "GST");
+        assert_eq!(payload.slots, Some(12));
+        assert_eq!(payload.proof_kinds, vec![ProofKind::Image, ProofKind::Link]);
+
+        assert!(parse_import("q.csv", "title,category,slots,schedule,platfrom\n").unwrap_err().contains("platfrom"));
+        assert!(parse_import("q.json", "{}").is_err());
+    }
+}
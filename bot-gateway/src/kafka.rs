@@ -1,4 +1,4 @@
-use rdkafka::producer::{FutureRecord};
+use rdkafka::producer::{FutureProducer, FutureRecord};
 use rdkafka::util::Timeout;
 use std::time::Duration;
 use crate::models::EventMessage;
@@ -8,6 +8,12 @@ type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, Data, Error>;
 
 pub async fn produce_event(ctx: Context<'_>, event_type: &str, payload: &impl serde::Serialize) -> Result<(), Error> {
+    send_event(&ctx.data().kafka_producer, event_type, payload).await
+}
+
+/// Same as [`produce_event`], for code running outside of a command (component
+/// handlers, gateway events).
+pub async fn send_event(producer: &FutureProducer, event_type: &str, payload: &impl serde::Serialize) -> Result<(), Error> {
     let payload_json = serde_json::to_string(payload)?;
     let event = EventMessage {
         event_type: event_type.to_string(),
@@ -15,7 +21,6 @@ pub async fn produce_event(ctx: Context<'_>, event_type: &str, payload: &impl se
     };
     let msg_json = serde_json::to_string(&event)?;
 
-    let producer = &ctx.data().kafka_producer;
     let record = FutureRecord::to("quest.events")
         .payload(&msg_json)
         .key(event_type);
@@ -24,4 +29,4 @@ pub async fn produce_event(ctx: Context<'_>, event_type: &str, payload: &impl se
         .map_err(|(e, _)| e)?;
 
     Ok(())
-}
\ No newline at end of file
+}
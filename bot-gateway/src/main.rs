@@ -7,10 +7,31 @@ mod commands {
     pub mod list;
     pub mod general;
     pub mod config;
+    pub mod notifications;
+    pub mod template;
+    pub mod party;
+    pub mod review;
+    pub mod roster;
+    pub mod checkin;
+    pub mod season;
 }
 mod security;
 mod api;
 mod cache;
+mod confirm;
+mod notify;
+mod digest;
+mod scheduler;
+mod announce;
+mod proof;
+mod storage;
+mod duplicates;
+mod export;
+mod events;
+mod voice;
+mod import;
+#[cfg(test)]
+mod test_util;
 
 use poise::serenity_prelude as serenity;
 use rdkafka::config::ClientConfig;
@@ -21,7 +42,8 @@ use std::{env, net::{SocketAddr}};
 use google_sheets4::{hyper, hyper_rustls, oauth2, Sheets};
 use serenity::{GuildId, RoleId};
 
-use crate::api::start_server;
+use crate::api::{start_server, ApiState};
+use crate::storage::ProofStore;
 
 pub type HubType = Sheets<hyper_rustls::HttpsConnector<hyper::client::HttpConnector>>;
 
@@ -33,6 +55,7 @@ pub struct Data {
     pub qg_role_id: RoleId,
     pub participant_role_id: RoleId,
     pub redis_client: RedisClient,
+    pub proof_store: Option<ProofStore>,
 }
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -53,6 +76,7 @@ async fn main() {
 
     let redis_url = env::var("REDIS_URL").expect("missing REDIS_URL");
     let redis_client = RedisClient::open(redis_url).expect("Invalid Redis URL");
+    let proof_store = ProofStore::from_env().expect("Invalid proof store settings");
 
     let sa_key_path = env::var("GOOGLE_APPLICATION_CREDENTIALS").unwrap_or("/app/credentials.json".to_string());
     let sheet_id = env::var("GOOGLE_SHEET_ID").expect("Missing GOOGLE_SHEET_ID");
@@ -77,34 +101,67 @@ async fn main() {
         .create()
         .expect("Producer creation error");
 
-    let producer_clone = producer.clone();
-    let redis_client_clone = redis_client.clone();
+    let api_state = ApiState {
+        producer: producer.clone(),
+        redis_client: redis_client.clone(),
+        sheets_hub: hub.clone(),
+        google_sheet_id: sheet_id.clone(),
+        guild_id: guild_id.get(),
+        export_token: env::var("EXPORT_API_TOKEN").ok().filter(|t| !t.is_empty()),
+    };
 
     spawn(async move {
-        start_server(producer_clone, addr, redis_client_clone).await;
+        start_server(api_state, addr).await;
     });
 
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
             commands: vec![
                 commands::quest::create(),
+                commands::quest::quest(),
                 commands::quest::edit(),
                 commands::quest::delete(), 
                 commands::quest::take(),
                 commands::quest::drop(),
                 commands::quest::submit(),
+                commands::quest::submissions(),
+                commands::review::review(),
+                commands::roster::roster(),
+                commands::checkin::checkin(),
+                commands::checkin::attendance(),
                 commands::quest::view(),
                 commands::stats::stats(),
+                commands::stats::history(),
                 commands::list::list(),
+                commands::list::search(),
                 commands::admin::register_community(),
+                commands::admin::export(),
+                commands::season::season(),
                 commands::general::help(),
                 commands::config::config(),
+                commands::notifications::notifications(),
+                commands::template::template(),
+                commands::party::party(),
             ],
+            event_handler: |ctx, event, _framework, data| {
+                Box::pin(events::handle_event(ctx, event, data))
+            },
             ..Default::default()
         })
         .setup(move |ctx, _ready, framework| {
             Box::pin(async move {
                 poise::builtins::register_in_guild(ctx, &framework.options().commands, guild_id).await?;
+
+                spawn(notify::run_notification_flusher(ctx.http.clone(), redis_client.clone()));
+                spawn(scheduler::run_scheduler(scheduler::JobContext {
+                    http: ctx.http.clone(),
+                    redis_client: redis_client.clone(),
+                    sheets_hub: hub.clone(),
+                    google_sheet_id: sheet_id.clone(),
+                    guild_id,
+                    participant_role_id: quest_participant_id,
+                    kafka_producer: producer.clone(),
+                }));
                 
                 Ok(Data {
                     kafka_producer: producer,
@@ -114,6 +171,7 @@ async fn main() {
                     qg_role_id: quest_giver_id,
                     participant_role_id: quest_participant_id,
                     redis_client: redis_client,
+                    proof_store,
                 })
             })
         })
@@ -34,11 +34,30 @@ pub struct QuestPayload {
     pub description: String,
     pub category: String,
     pub organizer_name: String,
-    pub slots: i8,
+    /// Participant cap; `None` is unlimited (`UNLIMITED` in the sheet).
+    pub slots: Option<u32>,
     pub schedule: String,
     pub platform: String,
     pub deadline: String,
     pub creator_id: String,
+    /// Source quest id when created with `/quest clone`.
+    pub cloned_from: Option<String>,
+    /// PUBLISHED, or DRAFT for quests saved with `draft: True`.
+    pub status: String,
+    /// Members per team for party quests; 0 or 1 means solo.
+    pub team_size: i8,
+    /// Quest ids that must be completed before this one can be taken.
+    pub prerequisites: Vec<String>,
+    /// Checkpoints needing their own proof; 0 or 1 means a single submission.
+    pub steps: i8,
+    /// Accepted proof kinds; empty means images only.
+    pub proof_kinds: Vec<ProofKind>,
+    /// Id of the season active when the quest was created; empty when no season is running.
+    pub season: String,
+    /// When the quest went live (column M); the announcement job picks it up from there.
+    /// `None` for drafts and imports, which are announced separately.
+    #[serde(default)]
+    pub publish_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -48,12 +67,44 @@ pub struct RegistrationPayload {
     pub user_tag: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartyMember {
+    pub user_id: String,
+    pub user_tag: String,
+}
+
+/// A group formed with `/party` to take team quests together. Kept in Redis;
+/// its id becomes the team id in the Participants tab.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Party {
+    pub party_id: String,
+    pub leader_id: String,
+    pub members: Vec<PartyMember>,
+}
+
+/// Where a proof file was first submitted, kept in Redis keyed by its hashes
+/// so later uploads of the same (or a near-identical) file can be flagged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofRecord {
+    pub quest_id: String,
+    pub user_id: String,
+    pub link: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamRegistrationPayload {
+    pub quest_id: String,
+    pub team_id: String,
+    pub members: Vec<PartyMember>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EditPayload {
     pub quest_id: String,
     pub title: String,
     pub description: String,
-    pub slots: i8,
+    /// Participant cap; `None` is unlimited.
+    pub slots: Option<u32>,
     pub schedule: String,
     pub deadline: String,
     pub platform: String,
@@ -62,6 +113,41 @@ pub struct EditPayload {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeletePayload {
     pub quest_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PublishPayload {
+    pub quest_id: String,
+    /// RFC3339 time to publish at; publish immediately when `None`.
+    pub publish_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplatePayload {
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub category: String,
+    pub organizer_name: String,
+    /// Participant cap; `None` is unlimited.
+    pub slots: Option<u32>,
+    pub platform: String,
+    pub duration_minutes: i64,
+    pub creator_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateSchedulePayload {
+    pub name: String,
+    pub rule: String,
+    pub lead_hours: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateStatusPayload {
+    pub name: String,
+    pub status: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -75,6 +161,92 @@ pub struct ProofPayload {
     pub quest_id: String,
     pub user_id: String,
     pub proof_url: String,
+    /// Checkpoint this proof is for (1-based).
+    pub step: i8,
+    /// Steps the quest has; the participant completes once every step has proof.
+    pub total_steps: i8,
+    pub proof_kind: ProofKind,
+}
+
+/// Participants an organizer marked as no-shows from `/roster`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NoShowPayload {
+    pub quest_id: String,
+    pub user_ids: Vec<String>,
+}
+
+/// A participant checking in at a live quest, from `/checkin` or the Check In button.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckInPayload {
+    pub quest_id: String,
+    pub user_id: String,
+}
+
+/// Binds a quest to a voice channel for automatic attendance; an empty
+/// `voice_channel_id` unbinds it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VoiceBindingPayload {
+    pub quest_id: String,
+    pub voice_channel_id: String,
+    pub minutes: u32,
+}
+
+/// A verifier's decision on one Submissions row (1-based `row_number`).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReviewPayload {
+    pub quest_id: String,
+    pub user_id: String,
+    pub row_number: usize,
+    /// APPROVED or REJECTED.
+    pub decision: String,
+    pub reviewer_id: String,
+    pub reason: String,
+    pub total_steps: i8,
+}
+
+/// What a proof submission is. Quests list the kinds they accept in the Quests tab
+/// (column Q); quests without a list only accept images.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ProofKind {
+    Image,
+    Document,
+    Audio,
+    Link,
+    Text,
+}
+
+impl ProofKind {
+    pub const ALL: [ProofKind; 5] = [
+        ProofKind::Image,
+        ProofKind::Document,
+        ProofKind::Audio,
+        ProofKind::Link,
+        ProofKind::Text,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProofKind::Image => "Image",
+            ProofKind::Document => "PDF",
+            ProofKind::Audio => "Audio",
+            ProofKind::Link => "Link",
+            ProofKind::Text => "Text",
+        }
+    }
+
+    /// Parses a sheet value (`IMAGE`, `DOCUMENT`, ...) or a user-typed name
+    /// (`image`, `pdf`, `audio`, `link`, `text`).
+    pub fn parse(value: &str) -> Option<ProofKind> {
+        match value.trim().to_lowercase().as_str() {
+            "image" => Some(ProofKind::Image),
+            "document" | "pdf" => Some(ProofKind::Document),
+            "audio" => Some(ProofKind::Audio),
+            "link" | "url" => Some(ProofKind::Link),
+            "text" => Some(ProofKind::Text),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -92,16 +264,162 @@ pub struct StatsResult {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct GuildConfig {
     // Channels
     pub announcement_channel_id: Option<u64>,
     pub proof_channel_id: Option<u64>,
     pub log_channel_id: Option<u64>,
+    pub notification_thread_id: Option<u64>,
     
     // Roles
     pub ping_role_id: Option<u64>,
     pub quest_giver_role_id: Option<u64>,
     pub verifier_role_id: Option<u64>,
+
+    // Digests
+    /// Time of day ("HH:MM", WIB) digests are posted. `None` disables digests.
+    pub digest_time: Option<String>,
+    pub daily_digest: bool,
+    pub weekly_digest_day: Option<DigestWeekday>,
+
+    // Attendance
+    /// Minutes before and after a quest's start during which check-in is open.
+    /// `None` uses the default window.
+    pub checkin_window_minutes: Option<u32>,
+
+    // Seasons
+    /// Every season so far, oldest first. The one without an end is active.
+    pub seasons: Vec<Season>,
+}
+
+impl GuildConfig {
+    /// The running season, if any.
+    pub fn active_season(&self) -> Option<&Season> {
+        self.seasons.iter().rev().find(|s| s.end.is_none())
+    }
+}
+
+/// An event season (cohort). Boards, stats and exports default to the active one.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Season {
+    pub id: String,
+    /// RFC 3339 start time.
+    pub start: String,
+    /// RFC 3339 end time, set when the season is closed.
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ChoiceParameter, Clone, Copy, PartialEq, Eq)]
+pub enum DigestWeekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl DigestWeekday {
+    pub fn to_chrono(self) -> chrono::Weekday {
+        match self {
+            DigestWeekday::Monday => chrono::Weekday::Mon,
+            DigestWeekday::Tuesday => chrono::Weekday::Tue,
+            DigestWeekday::Wednesday => chrono::Weekday::Wed,
+            DigestWeekday::Thursday => chrono::Weekday::Thu,
+            DigestWeekday::Friday => chrono::Weekday::Fri,
+            DigestWeekday::Saturday => chrono::Weekday::Sat,
+            DigestWeekday::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ChoiceParameter, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationCategory {
+    #[name = "New Quest Pings"]
+    NewQuests,
+    #[name = "Reminders"]
+    Reminders,
+    #[name = "Status Changes (edits & cancellations)"]
+    StatusChanges,
+    #[name = "Digests"]
+    Digests,
+}
+
+impl NotificationCategory {
+    pub const ALL: [NotificationCategory; 4] = [
+        NotificationCategory::NewQuests,
+        NotificationCategory::Reminders,
+        NotificationCategory::StatusChanges,
+        NotificationCategory::Digests,
+    ];
+
+    /// Redis key suffix, also used for the subscriber set of opt-in categories.
+    pub fn key(self) -> &'static str {
+        match self {
+            NotificationCategory::NewQuests => "new_quests",
+            NotificationCategory::Reminders => "reminders",
+            NotificationCategory::StatusChanges => "status_changes",
+            NotificationCategory::Digests => "digests",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ChoiceParameter, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotificationDelivery {
+    #[default]
+    #[name = "Direct Message"]
+    Dm,
+    #[name = "Mention in notification thread"]
+    Thread,
+}
+
+/// Per-user notification preferences, stored in Redis under `notify_prefs:<user_id>`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct NotificationPrefs {
+    pub new_quests: bool,
+    pub reminders: bool,
+    /// Edits and cancellations of quests the user took.
+    pub status_changes: bool,
+    pub digests: bool,
+    pub delivery: NotificationDelivery,
+    /// (start_hour, end_hour) in WIB, end exclusive. Wraps past midnight when start > end.
+    pub quiet_hours: Option<(u8, u8)>,
+}
+
+impl Default for NotificationPrefs {
+    fn default() -> Self {
+        Self {
+            new_quests: false,
+            reminders: true,
+            status_changes: true,
+            digests: false,
+            delivery: NotificationDelivery::Dm,
+            quiet_hours: None,
+        }
+    }
+}
+
+impl NotificationPrefs {
+    pub fn is_enabled(&self, category: NotificationCategory) -> bool {
+        match category {
+            NotificationCategory::NewQuests => self.new_quests,
+            NotificationCategory::Reminders => self.reminders,
+            NotificationCategory::StatusChanges => self.status_changes,
+            NotificationCategory::Digests => self.digests,
+        }
+    }
+
+    pub fn set_enabled(&mut self, category: NotificationCategory, enabled: bool) {
+        match category {
+            NotificationCategory::NewQuests => self.new_quests = enabled,
+            NotificationCategory::Reminders => self.reminders = enabled,
+            NotificationCategory::StatusChanges => self.status_changes = enabled,
+            NotificationCategory::Digests => self.digests = enabled,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
@@ -112,6 +430,8 @@ pub enum ChannelConfigType {
     Proof,
     #[name = "Log Channel"]
     Log,
+    #[name = "Notification Thread"]
+    Notification,
 }
 
 #[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
@@ -123,10 +443,38 @@ pub enum RoleConfigType {
     #[name = "Verifier Role"]
     Verifier,
 }
+
+/// `/list` status filter, matching `common::QuestStatus`.
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum StatusFilter {
+    #[name = "Upcoming"]
+    Upcoming,
+    #[name = "Ongoing"]
+    Ongoing,
+    #[name = "Ended"]
+    Ended,
+    #[name = "TBA"]
+    Tba,
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum QuestSort {
+    #[name = "Start time"]
+    Start,
+    #[name = "Deadline"]
+    Deadline,
+}
+
 pub enum QuestCompleteMode {
     Take,
     Submit,
     View,
+    /// Every quest, including ended ones (e.g. as a clone source).
+    All,
+    /// Drafts and quests scheduled for publishing.
+    Draft,
+    /// Quests the user has taken, in any status except dropped.
+    Mine,
 }
 
 #[cfg(test)]
@@ -152,7 +500,15 @@ mod tests {
             platform: "Discord".to_string(),
             deadline: "2025-01-02T10:00:00+07:00".to_string(),
             creator_id: "999".to_string(),
-            slots: 5,
+            slots: Some(5),
+            cloned_from: None,
+            status: "PUBLISHED".to_string(),
+            team_size: 0,
+            prerequisites: Vec::new(),
+            steps: 0,
+            proof_kinds: Vec::new(),
+            season: String::new(),
+            publish_at: None,
         };
         
         let json = serde_json::to_string(&payload).unwrap();
@@ -160,6 +516,25 @@ mod tests {
         assert!(json.contains("\"slots\":5"));
     }
 
+    #[test]
+    fn test_proof_kind_sheet_round_trip() {
+        for kind in ProofKind::ALL {
+            let stored = serde_json::to_string(&kind).unwrap();
+            assert_eq!(ProofKind::parse(stored.trim_matches('"')), Some(kind));
+        }
+        assert_eq!(ProofKind::parse(" PDF "), Some(ProofKind::Document));
+        assert_eq!(ProofKind::parse("video"), None);
+    }
+
+    #[test]
+    fn test_notification_prefs_defaults_for_missing_fields() {
+        let prefs: NotificationPrefs = serde_json::from_str(r#"{"status_changes":false}"#).unwrap();
+        assert!(!prefs.is_enabled(NotificationCategory::StatusChanges));
+        assert!(prefs.is_enabled(NotificationCategory::Reminders));
+        assert_eq!(prefs.delivery, NotificationDelivery::Dm);
+        assert!(prefs.quiet_hours.is_none());
+    }
+
     #[test]
     fn test_event_message_wrapper() {
         let inner_payload = RegistrationPayload {
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{FixedOffset, Timelike, Utc};
+use poise::serenity_prelude as serenity;
+use redis::{AsyncCommands, Client as RedisClient};
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, CreateAllowedMentions, CreateMessage, UserId};
+
+use crate::cache::{get_category_subscribers, get_notification_prefs};
+use crate::models::{GuildConfig, NotificationCategory, NotificationDelivery, NotificationPrefs};
+
+/// A notification held back because it arrived during the user's quiet hours.
+#[derive(Debug, Serialize, Deserialize)]
+struct QueuedNotification {
+    content: String,
+    thread_channel_id: Option<u64>,
+}
+
+/// Whether `hour` (0-23, WIB) falls inside the quiet window. The end hour is exclusive
+/// and a window with start > end wraps past midnight.
+pub fn in_quiet_hours(hour: u8, quiet_hours: Option<(u8, u8)>) -> bool {
+    match quiet_hours {
+        Some((start, end)) if start < end => hour >= start && hour < end,
+        Some((start, end)) if start > end => hour >= start || hour < end,
+        _ => false,
+    }
+}
+
+fn current_wib_hour() -> u8 {
+    let wib_offset = FixedOffset::east_opt(7 * 3600).unwrap();
+    Utc::now().with_timezone(&wib_offset).hour() as u8
+}
+
+/// Sends a DM to a user by id, logging (not failing) when their DMs are closed.
+pub async fn send_dm(http: &serenity::Http, user_id: &str, message: CreateMessage) {
+    let Ok(id) = user_id.parse::<u64>() else {
+        eprintln!("Skipping DM to invalid user id `{}`", user_id);
+        return;
+    };
+
+    match UserId::new(id).create_dm_channel(http).await {
+        Ok(channel) => {
+            if let Err(e) = channel.send_message(http, message).await {
+                eprintln!("Failed to DM user {}: {:?}", user_id, e);
+            }
+        }
+        Err(e) => eprintln!("Failed to open DM with user {}: {:?}", user_id, e),
+    }
+}
+
+/// Delivers one notification using the user's chosen delivery method.
+/// Thread delivery falls back to DM when no notification thread is configured.
+async fn deliver(
+    http: &serenity::Http,
+    user_id: &str,
+    delivery: NotificationDelivery,
+    thread_channel_id: Option<u64>,
+    content: &str,
+) {
+    match (delivery, thread_channel_id) {
+        (NotificationDelivery::Thread, Some(thread_id)) => {
+            let Ok(id) = user_id.parse::<u64>() else {
+                eprintln!("Skipping mention of invalid user id `{}`", user_id);
+                return;
+            };
+            let message = CreateMessage::new()
+                .content(format!("<@{}> {}", user_id, content))
+                .allowed_mentions(CreateAllowedMentions::new().users(vec![UserId::new(id)]));
+            if let Err(e) = ChannelId::new(thread_id).send_message(http, message).await {
+                eprintln!("Failed to mention user {} in notification thread: {:?}", user_id, e);
+            }
+        }
+        _ => send_dm(http, user_id, CreateMessage::new().content(content)).await,
+    }
+}
+
+async fn queue_for_later(redis_client: &RedisClient, user_id: &str, item: &QueuedNotification) -> Result<(), crate::Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let json = serde_json::to_string(item)?;
+    let _: () = con.rpush(format!("notify_queue:{}", user_id), json).await?;
+    let _: () = con.sadd("notify_queue_users", user_id).await?;
+    Ok(())
+}
+
+/// Notifies every user in `user_ids` who has `category` enabled, honoring their
+/// delivery method and quiet hours. Returns how many users were notified or queued.
+pub async fn notify_users(
+    http: &serenity::Http,
+    redis_client: &RedisClient,
+    config: &GuildConfig,
+    user_ids: &[String],
+    category: NotificationCategory,
+    content: &str,
+) -> usize {
+    let hour = current_wib_hour();
+    let mut sent = 0;
+
+    for user_id in user_ids {
+        let prefs = get_notification_prefs(redis_client, user_id).await.unwrap_or_default();
+        if !prefs.is_enabled(category) {
+            continue;
+        }
+
+        if in_quiet_hours(hour, prefs.quiet_hours) {
+            let item = QueuedNotification {
+                content: content.to_string(),
+                thread_channel_id: config.notification_thread_id,
+            };
+            match queue_for_later(redis_client, user_id, &item).await {
+                Ok(_) => sent += 1,
+                Err(e) => eprintln!("Failed to queue notification for {}: {:?}", user_id, e),
+            }
+            continue;
+        }
+
+        deliver(http, user_id, prefs.delivery, config.notification_thread_id, content).await;
+        sent += 1;
+    }
+    sent
+}
+
+/// Notifies everyone who opted in to `category` (e.g. new quest pings).
+pub async fn notify_subscribers(
+    http: &serenity::Http,
+    redis_client: &RedisClient,
+    config: &GuildConfig,
+    category: NotificationCategory,
+    content: &str,
+) -> usize {
+    match get_category_subscribers(redis_client, category).await {
+        Ok(user_ids) => notify_users(http, redis_client, config, &user_ids, category, content).await,
+        Err(e) => {
+            eprintln!("Failed to read {} subscribers: {:?}", category.key(), e);
+            0
+        }
+    }
+}
+
+/// Delivers notifications queued during quiet hours to users whose quiet hours are over.
+pub async fn flush_queued_notifications(http: &serenity::Http, redis_client: &RedisClient) -> Result<(), crate::Error> {
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let user_ids: Vec<String> = con.smembers("notify_queue_users").await?;
+    let hour = current_wib_hour();
+
+    for user_id in user_ids {
+        let prefs: NotificationPrefs = get_notification_prefs(redis_client, &user_id).await.unwrap_or_default();
+        if in_quiet_hours(hour, prefs.quiet_hours) {
+            continue;
+        }
+
+        // Read and clear the queue in one MULTI/EXEC so items queued meanwhile aren't lost.
+        let queue_key = format!("notify_queue:{}", user_id);
+        let (items,): (Vec<String>,) = redis::pipe()
+            .atomic()
+            .lrange(&queue_key, 0, -1)
+            .del(&queue_key).ignore()
+            .srem("notify_queue_users", &user_id).ignore()
+            .query_async(&mut con)
+            .await?;
+
+        for json in items {
+            match serde_json::from_str::<QueuedNotification>(&json) {
+                Ok(item) => deliver(http, &user_id, prefs.delivery, item.thread_channel_id, &item.content).await,
+                Err(e) => eprintln!("Dropping malformed queued notification for {}: {:?}", user_id, e),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Background loop flushing the quiet-hours queue every five minutes.
+pub async fn run_notification_flusher(http: Arc<serenity::Http>, redis_client: RedisClient) {
+    let mut interval = tokio::time::interval(Duration::from_secs(300));
+    loop {
+        interval.tick().await;
+        if let Err(e) = flush_queued_notifications(&http, &redis_client).await {
+            eprintln!("Failed to flush queued notifications: {:?}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_hours_same_day() {
+        assert!(in_quiet_hours(13, Some((12, 14))));
+        assert!(!in_quiet_hours(14, Some((12, 14))));
+        assert!(!in_quiet_hours(11, Some((12, 14))));
+    }
+
+    #[test]
+    fn test_quiet_hours_wraps_midnight() {
+        assert!(in_quiet_hours(23, Some((22, 7))));
+        assert!(in_quiet_hours(3, Some((22, 7))));
+        assert!(!in_quiet_hours(7, Some((22, 7))));
+        assert!(!in_quiet_hours(12, Some((22, 7))));
+    }
+
+    #[test]
+    fn test_quiet_hours_disabled() {
+        assert!(!in_quiet_hours(3, None));
+        assert!(!in_quiet_hours(3, Some((5, 5))));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::models::ProofKind;
+
+const MB: u32 = 1024 * 1024;
+
+/// Hosts accepted for link proofs (subdomains included), e.g. itch.io pages,
+/// YouTube videos and Google Drive / Docs shares.
+const LINK_ALLOW_LIST: [&str; 5] = ["itch.io", "youtube.com", "youtu.be", "drive.google.com", "docs.google.com"];
+
+const MAX_TEXT_LEN: usize = 1500;
+
+/// Size limit for an uploaded proof of the given kind.
+fn max_size(kind: ProofKind) -> u32 {
+    match kind {
+        ProofKind::Image => 10 * MB,
+        _ => 25 * MB,
+    }
+}
+
+/// Works out which kind an uploaded attachment is from its content type.
+pub fn classify_attachment(content_type: Option<&str>) -> Option<ProofKind> {
+    let content_type = content_type?;
+    if content_type.starts_with("image/") {
+        Some(ProofKind::Image)
+    } else if content_type.starts_with("audio/") {
+        Some(ProofKind::Audio)
+    } else if content_type.starts_with("application/pdf") {
+        Some(ProofKind::Document)
+    } else {
+        None
+    }
+}
+
+/// Checks an attachment against the quest's accepted kinds and the size limit.
+pub fn validate_attachment(allowed: &[ProofKind], content_type: Option<&str>, size: u32) -> Result<ProofKind, String> {
+    let kind = classify_attachment(content_type)
+        .ok_or_else(|| "Unsupported file type. Upload an image, a PDF or an audio file.".to_string())?;
+    if !allowed.contains(&kind) {
+        return Err(format!("This quest doesn't accept {} proofs.", kind.label()));
+    }
+    if size > max_size(kind) {
+        return Err(format!("{} proofs can be at most {} MB.", kind.label(), max_size(kind) / MB));
+    }
+    Ok(kind)
+}
+
+/// Accepts `https://` links whose host is on the allow-list.
+pub fn validate_link(link: &str) -> Result<(), String> {
+    let rest = link.trim().strip_prefix("https://")
+        .ok_or_else(|| "Links must start with https://".to_string())?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("").to_lowercase();
+    let host = host.split(':').next().unwrap_or("");
+
+    let allowed = LINK_ALLOW_LIST.iter().any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)));
+    if !allowed {
+        return Err(format!("Links must point to one of: {}.", LINK_ALLOW_LIST.join(", ")));
+    }
+    Ok(())
+}
+
+pub fn validate_text(text: &str) -> Result<(), String> {
+    let len = text.trim().chars().count();
+    if len == 0 {
+        return Err("Text proof can't be empty.".to_string());
+    }
+    if len > MAX_TEXT_LEN {
+        return Err(format!("Text proof can be at most {} characters.", MAX_TEXT_LEN));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_attachment() {
+        let allowed = [ProofKind::Image, ProofKind::Document];
+        assert_eq!(validate_attachment(&allowed, Some("image/png"), MB), Ok(ProofKind::Image));
+        assert_eq!(validate_attachment(&allowed, Some("application/pdf"), 20 * MB), Ok(ProofKind::Document));
+        assert!(validate_attachment(&allowed, Some("image/png"), 11 * MB).is_err());
+        assert!(validate_attachment(&allowed, Some("audio/mpeg"), MB).is_err());
+        assert!(validate_attachment(&allowed, Some("application/zip"), MB).is_err());
+        assert!(validate_attachment(&allowed, None, MB).is_err());
+    }
+
+    #[test]
+    fn test_validate_link() {
+        assert!(validate_link("https://someone.itch.io/game").is_ok());
+        assert!(validate_link("https://www.youtube.com/watch?v=abc").is_ok());
+        assert!(validate_link("https://youtu.be/abc").is_ok());
+        assert!(validate_link("https://drive.google.com/file/d/xyz/view").is_ok());
+        assert!(validate_link("http://youtube.com/watch?v=abc").is_err());
+        assert!(validate_link("https://evil-youtube.com/watch").is_err());
+        assert!(validate_link("https://itch.io.evil.com/").is_err());
+    }
+
+    #[test]
+    fn test_validate_text() {
+        assert!(validate_text("Wrote 3 chapters").is_ok());
+        assert!(validate_text("   ").is_err());
+        assert!(validate_text(&"a".repeat(MAX_TEXT_LEN + 1)).is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use rdkafka::producer::FutureProducer;
+use redis::Client as RedisClient;
+use serenity::{GuildId, RoleId};
+
+use crate::{announce, digest, voice, HubType};
+
+/// Everything a background job needs, cloned out of `Data` at startup
+/// since jobs run outside of any command context.
+#[derive(Clone)]
+pub struct JobContext {
+    pub http: Arc<serenity::Http>,
+    pub redis_client: RedisClient,
+    pub sheets_hub: HubType,
+    pub google_sheet_id: String,
+    pub guild_id: GuildId,
+    pub participant_role_id: RoleId,
+    pub kafka_producer: FutureProducer,
+}
+
+/// Runs the gateway's periodic jobs once a minute.
+pub async fn run_scheduler(job: JobContext) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = announce::announce_due_quests(&job).await {
+            eprintln!("Scheduled announcement job failed: {:?}", e);
+        }
+
+        if let Err(e) = digest::post_due_digests(&job).await {
+            eprintln!("Digest job failed: {:?}", e);
+        }
+
+        if let Err(e) = voice::flush_voice_sessions(&job).await {
+            eprintln!("Voice attendance job failed: {:?}", e);
+        }
+    }
+}
@@ -38,11 +38,8 @@ pub async fn check_admin(ctx: Context<'_>) -> Result<bool, Error> {
     Ok(true)
 }
 
-pub async fn check_quest_role(ctx: Context<'_>) -> Result<bool, Error> {
-    if !check_guild(ctx).await? {
-        return Ok(false);
-    }
-
+/// Whether the invoking member is staff (QuestRole or admin), without replying.
+pub async fn is_quest_giver(ctx: Context<'_>) -> Result<bool, Error> {
     let quest_role = ctx.data().qg_role_id;
     let user = ctx.author_member().await.ok_or("Failed to get member")?;
 
@@ -50,7 +47,15 @@ pub async fn check_quest_role(ctx: Context<'_>) -> Result<bool, Error> {
 
     let is_admin = user.permissions.map(|p| p.administrator()).unwrap_or(false);
 
-    if has_role || is_admin {
+    Ok(has_role || is_admin)
+}
+
+pub async fn check_quest_role(ctx: Context<'_>) -> Result<bool, Error> {
+    if !check_guild(ctx).await? {
+        return Ok(false);
+    }
+
+    if is_quest_giver(ctx).await? {
         Ok(true)
     } else {
         ctx.send(poise::CreateReply::default()
@@ -61,25 +66,54 @@ pub async fn check_quest_role(ctx: Context<'_>) -> Result<bool, Error> {
     }
 }
 
+/// Whether the invoking member has the participant role (or is an admin), without replying.
+pub async fn is_participant(ctx: Context<'_>) -> Result<bool, Error> {
+    let participant_role = ctx.data().participant_role_id;
+    let user = ctx.author_member().await.ok_or("Failed to get member")?;
+
+    let has_role = user.roles.contains(&participant_role);
+
+    let is_admin = user.permissions.map(|p| p.administrator()).unwrap_or(false);
+
+    Ok(has_role || is_admin)
+}
+
 pub async fn check_participant_role(ctx: Context<'_>) -> Result<bool, Error> {
     if !check_guild(ctx).await? {
         return Ok(false);
     }
 
-    let participant_role = ctx.data().participant_role_id;
-    let user = ctx.author_member().await.ok_or("Failed to get member")?;
+    if is_participant(ctx).await? {
+        Ok(true)
+    } else {
+        ctx.send(poise::CreateReply::default()
+            .content("⛔ Access Denied: Only CaStaff can use this command.")
+            .ephemeral(true)
+        ).await?;
+        Ok(false)
+    }
+}
 
-    let has_role = user.roles.contains(&participant_role);
+/// Admins, or members with the verifier role from `/config` (admin only when unset).
+pub async fn check_verifier(ctx: Context<'_>) -> Result<bool, Error> {
+    if !check_guild(ctx).await? {
+        return Ok(false);
+    }
 
+    let user = ctx.author_member().await.ok_or("Failed to get member")?;
     let is_admin = user.permissions.map(|p| p.administrator()).unwrap_or(false);
 
+    let guild_id = ctx.guild_id().ok_or("Must be in guild")?;
+    let config = crate::cache::get_guild_config(ctx, guild_id.get()).await.unwrap_or_default();
+    let has_role = config.verifier_role_id.is_some_and(|id| user.roles.iter().any(|r| r.get() == id));
+
     if has_role || is_admin {
         Ok(true)
     } else {
         ctx.send(poise::CreateReply::default()
-            .content("⛔ Access Denied: Only CaStaff can use this command.")
+            .content("⛔ Access Denied: Only verifiers can review submissions.")
             .ephemeral(true)
         ).await?;
         Ok(false)
     }
-}
\ No newline at end of file
+}
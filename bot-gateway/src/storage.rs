@@ -0,0 +1,193 @@
+use std::env;
+use std::path::PathBuf;
+
+use chrono::Utc;
+use ring::{digest, hmac};
+
+use crate::Error;
+
+/// A proof file saved outside of Discord, whose CDN links expire.
+#[derive(Debug, Clone)]
+pub struct StoredProof {
+    /// Public URL, or the bucket URL when an S3 store has no public base URL.
+    pub location: String,
+    /// Hex SHA-256 of the file contents, also used as its file name.
+    pub sha256: String,
+}
+
+/// Files on a local volume, served by something outside the bot (a web server or
+/// CDN) at `public_base_url`.
+#[derive(Debug, Clone)]
+pub struct LocalStore {
+    dir: PathBuf,
+    public_base_url: String,
+}
+
+/// Any S3-compatible bucket (AWS S3, MinIO, ...), addressed path-style.
+#[derive(Debug, Clone)]
+pub struct S3Store {
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+    public_base_url: Option<String>,
+    client: reqwest::Client,
+}
+
+/// Where submitted proof files are persisted, picked with `PROOF_STORE`.
+#[derive(Debug, Clone)]
+pub enum ProofStore {
+    Local(LocalStore),
+    S3(S3Store),
+}
+
+fn required(name: &str) -> Result<String, Error> {
+    env::var(name).map_err(|_| format!("missing {}", name).into())
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(digest::digest(&digest::SHA256, bytes))
+}
+
+/// Content-addressed object name: `<sha256>.<ext>`, keeping a short alphanumeric
+/// extension from the uploaded file name.
+pub fn content_key(sha256: &str, filename: &str) -> String {
+    match filename.rsplit_once('.') {
+        Some((_, ext)) if !ext.is_empty() && ext.len() <= 8 && ext.chars().all(|c| c.is_ascii_alphanumeric()) => {
+            format!("{}.{}", sha256, ext.to_lowercase())
+        }
+        _ => sha256.to_string(),
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::sign(&key, data.as_bytes()).as_ref().to_vec()
+}
+
+/// AWS Signature V4 signing key for a day, region and service.
+fn signing_key(secret_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{}", secret_key).as_bytes(), date);
+    let k_region = hmac_sha256(&k_date, region);
+    let k_service = hmac_sha256(&k_region, service);
+    hmac_sha256(&k_service, "aws4_request")
+}
+
+impl ProofStore {
+    /// Reads the store settings from the environment. `PROOF_STORE` is `local`, `s3`
+    /// or unset, in which case proofs keep pointing at Discord's CDN. `local` needs
+    /// `PROOF_PUBLIC_BASE_URL`: a path inside the container is useless to verifiers.
+    pub fn from_env() -> Result<Option<ProofStore>, Error> {
+        let public_base_url = env::var("PROOF_PUBLIC_BASE_URL").ok()
+            .map(|u| u.trim().trim_end_matches('/').to_string())
+            .filter(|u| !u.is_empty());
+
+        match env::var("PROOF_STORE").unwrap_or_default().to_lowercase().as_str() {
+            "" | "none" => Ok(None),
+            "local" => Ok(Some(ProofStore::Local(LocalStore {
+                dir: PathBuf::from(env::var("PROOF_STORE_DIR").unwrap_or("/app/proofs".to_string())),
+                public_base_url: public_base_url
+                    .ok_or("PROOF_STORE=local needs PROOF_PUBLIC_BASE_URL, the URL the stored files are served from")?,
+            }))),
+            "s3" => Ok(Some(ProofStore::S3(S3Store {
+                endpoint: required("S3_ENDPOINT")?.trim_end_matches('/').to_string(),
+                bucket: required("S3_BUCKET")?,
+                region: env::var("S3_REGION").unwrap_or("us-east-1".to_string()),
+                access_key: required("S3_ACCESS_KEY")?,
+                secret_key: required("S3_SECRET_KEY")?,
+                public_base_url,
+                client: reqwest::Client::new(),
+            }))),
+            other => Err(format!("unknown PROOF_STORE `{}` (use local or s3)", other).into()),
+        }
+    }
+
+    /// Saves the file under its content hash. Saving the same bytes twice is harmless.
+    pub async fn put(&self, bytes: &[u8], filename: &str, content_type: &str) -> Result<StoredProof, Error> {
+        let sha256 = sha256_hex(bytes);
+        let key = content_key(&sha256, filename);
+
+        let location = match self {
+            ProofStore::Local(store) => store.put(&key, bytes).await?,
+            ProofStore::S3(store) => store.put(&key, bytes, content_type).await?,
+        };
+        Ok(StoredProof { location, sha256 })
+    }
+}
+
+impl LocalStore {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<String, Error> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let path = self.dir.join(key);
+        tokio::fs::write(&path, bytes).await?;
+
+        Ok(format!("{}/{}", self.public_base_url, key))
+    }
+}
+
+impl S3Store {
+    async fn put(&self, key: &str, bytes: &[u8], content_type: &str) -> Result<String, Error> {
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let host = self.endpoint.split("://").nth(1).unwrap_or(&self.endpoint);
+        let path = format!("/{}/{}", self.bucket, key);
+        let payload_hash = sha256_hex(bytes);
+
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            path, host, payload_hash, amz_date, signed_headers, payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date, scope, sha256_hex(canonical_request.as_bytes())
+        );
+        let signature = hex::encode(hmac_sha256(&signing_key(&self.secret_key, &date, &self.region, "s3"), &string_to_sign));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key, scope, signed_headers, signature
+        );
+
+        let url = format!("{}{}", self.endpoint, path);
+        let response = self.client.put(&url)
+            .header("x-amz-date", &amz_date)
+            .header("x-amz-content-sha256", &payload_hash)
+            .header("authorization", authorization)
+            .header("content-type", content_type)
+            .body(bytes.to_vec())
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(format!("S3 upload failed with {}", response.status()).into());
+        }
+
+        Ok(match &self.public_base_url {
+            Some(base) => format!("{}/{}", base, key),
+            None => url,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_key() {
+        assert_eq!(content_key("abc", "proof.PNG"), "abc.png");
+        assert_eq!(content_key("abc", "track.final.mp3"), "abc.mp3");
+        assert_eq!(content_key("abc", "no_extension"), "abc");
+        assert_eq!(content_key("abc", "weird.p/ng"), "abc");
+    }
+
+    #[test]
+    fn test_signing_key_matches_aws_example() {
+        // Example from the AWS Signature Version 4 documentation.
+        let key = signing_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", "20120215", "us-east-1", "iam");
+        assert_eq!(hex::encode(key), "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d");
+    }
+}
@@ -0,0 +1,6 @@
+//! Helpers shared by the unit tests.
+
+/// Builds a sheet row from string literals.
+pub fn s(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
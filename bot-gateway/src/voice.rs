@@ -0,0 +1,252 @@
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude as serenity;
+use rdkafka::producer::FutureProducer;
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, Client as RedisClient, Script};
+use serde::{Deserialize, Serialize};
+use serenity::all::{Guild, VoiceState};
+
+use crate::cache::{fetch_sheet_data, quest_is_published, quest_step_count, quest_voice_channel, quest_voice_minutes, read_guild_config};
+use crate::kafka::send_event;
+use crate::models::{NotificationCategory, ProofKind, ProofPayload};
+use crate::notify::notify_users;
+use crate::scheduler::JobContext;
+use crate::{Data, Error, HubType};
+
+/// Minutes of presence needed when a binding doesn't say otherwise.
+pub const DEFAULT_VOICE_MINUTES: u32 = 30;
+
+/// Redis hash of user id -> [`VoiceSession`] for members currently in a voice channel.
+const SESSIONS_KEY: &str = "voice_sessions";
+
+/// Replaces a member's session (ARGV[1]) only if it still reads ARGV[2] (empty = no
+/// session), deleting it when ARGV[3] is empty. Returns 1 when swapped.
+const SWAP_SESSION_SCRIPT: &str = r#"
+local current = redis.call('HGET', KEYS[1], ARGV[1]) or ''
+if current ~= ARGV[2] then
+    return 0
+end
+if ARGV[3] == '' then
+    redis.call('HDEL', KEYS[1], ARGV[1])
+else
+    redis.call('HSET', KEYS[1], ARGV[1], ARGV[3])
+end
+return 1
+"#;
+
+/// Where a member is sitting and since when their time was last counted.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct VoiceSession {
+    pub channel_id: u64,
+    pub since: i64,
+}
+
+/// Everything needed to credit voice time, from either the gateway event
+/// handler or the scheduler.
+pub struct VoiceTracker<'a> {
+    pub http: &'a serenity::Http,
+    pub redis_client: &'a RedisClient,
+    pub sheets_hub: &'a HubType,
+    pub google_sheet_id: &'a str,
+    pub kafka_producer: &'a FutureProducer,
+    pub guild_id: u64,
+}
+
+impl<'a> VoiceTracker<'a> {
+    pub fn from_data(http: &'a serenity::Http, data: &'a Data) -> Self {
+        VoiceTracker {
+            http,
+            redis_client: &data.redis_client,
+            sheets_hub: &data.sheets_hub,
+            google_sheet_id: &data.google_sheet_id,
+            kafka_producer: &data.kafka_producer,
+            guild_id: data.target_guild_id.get(),
+        }
+    }
+
+    pub fn from_job(job: &'a JobContext) -> Self {
+        VoiceTracker {
+            http: &job.http,
+            redis_client: &job.redis_client,
+            sheets_hub: &job.sheets_hub,
+            google_sheet_id: &job.google_sheet_id,
+            kafka_producer: &job.kafka_producer,
+            guild_id: job.guild_id.get(),
+        }
+    }
+}
+
+/// Seconds of `[since, until]` that fall inside the quest's `[start, end]`.
+pub fn overlap_seconds(since: i64, until: i64, start: i64, end: i64) -> i64 {
+    (until.min(end) - since.max(start)).max(0)
+}
+
+/// Adds the time since `session.since` to every quest bound to the session's channel
+/// that the member is taking, and completes the quests whose threshold is reached.
+async fn credit_session(tracker: &VoiceTracker<'_>, user_id: &str, session: VoiceSession, now: i64) -> Result<(), Error> {
+    if now <= session.since {
+        return Ok(());
+    }
+    let data = fetch_sheet_data(tracker.redis_client, tracker.sheets_hub, tracker.google_sheet_id).await?;
+    let ts = |s: &str| DateTime::parse_from_rfc3339(s).ok().map(|dt| dt.timestamp());
+    let mut con = tracker.redis_client.get_multiplexed_async_connection().await?;
+
+    // column mapping (0-based): 0: quest_id, 1: title, 5: schedule, 8: deadline, 18: voice_channel_id
+    for row in data.q_rows.iter().skip(1) {
+        if row.len() < 9 || !quest_is_published(row) || quest_voice_channel(row) != Some(session.channel_id) {
+            continue;
+        }
+        let (Some(start), Some(end)) = (ts(&row[5]), ts(&row[8])) else {
+            continue;
+        };
+        let seconds = overlap_seconds(session.since, now, start, end);
+        if seconds == 0 {
+            continue;
+        }
+        let taking = data.p_rows.iter().skip(1).any(|p| {
+            p.len() >= 4 && p[0] == row[0] && p[1] == user_id && (p[3] == "ON_PROGRESS" || p[3] == "CHECKED_IN")
+        });
+        if !taking {
+            continue;
+        }
+
+        let total: i64 = con.hincr(format!("voice_seconds:{}", row[0]), user_id, seconds).await?;
+        let minutes = total / 60;
+        if minutes < quest_voice_minutes(row) as i64 {
+            continue;
+        }
+        let newly_completed: i64 = con.sadd(format!("voice_completed:{}", row[0]), user_id).await?;
+        if newly_completed == 0 {
+            continue;
+        }
+
+        // Attendance counts as step 1; multi-step quests still need the remaining proofs.
+        let steps = quest_step_count(row);
+        send_event(tracker.kafka_producer, "SUBMIT_PROOF", &ProofPayload {
+            quest_id: row[0].clone(),
+            user_id: user_id.to_string(),
+            proof_url: format!("Voice attendance: {} minutes in <#{}>", minutes, session.channel_id),
+            step: 1,
+            total_steps: steps,
+            proof_kind: ProofKind::Text,
+        }).await?;
+        println!("Voice attendance recorded for quest {} by {} ({} min)", row[0], user_id, minutes);
+
+        let message = if steps > 1 {
+            format!("🎙️ You spent {} minutes in <#{}>, so step 1 of {} for **{}** is recorded. Use `/submit` for the remaining steps.", minutes, session.channel_id, steps, row[1])
+        } else {
+            format!("🎙️ You spent {} minutes in <#{}>, so **{}** is completed and waiting for review.", minutes, session.channel_id, row[1])
+        };
+        let config = read_guild_config(tracker.redis_client, tracker.guild_id).await.unwrap_or_default();
+        notify_users(
+            tracker.http,
+            tracker.redis_client,
+            &config,
+            &[user_id.to_string()],
+            NotificationCategory::StatusChanges,
+            &message,
+        ).await;
+    }
+    Ok(())
+}
+
+/// Atomically swaps a member's stored session from `expected` to `replacement`
+/// (empty strings meaning none). Whoever swaps a session out credits its time, so
+/// the gateway handler and the flush job never count the same seconds twice, and
+/// the flush can't bring back a session that was closed in the meantime.
+async fn swap_session(con: &mut MultiplexedConnection, user_id: &str, expected: &str, replacement: &str) -> Result<bool, Error> {
+    let swapped: i32 = Script::new(SWAP_SESSION_SCRIPT)
+        .key(SESSIONS_KEY)
+        .arg(user_id)
+        .arg(expected)
+        .arg(replacement)
+        .invoke_async(con)
+        .await?;
+    Ok(swapped == 1)
+}
+
+/// Gateway `voice_state_update`: credits the time spent where the member was,
+/// then starts counting wherever they are now.
+pub async fn handle_voice_update(tracker: &VoiceTracker<'_>, state: &VoiceState) -> Result<(), Error> {
+    if state.guild_id.map(|id| id.get()) != Some(tracker.guild_id) || state.member.as_ref().is_some_and(|m| m.user.bot) {
+        return Ok(());
+    }
+    let user_id = state.user_id.to_string();
+    let now = Utc::now().timestamp();
+    let mut con = tracker.redis_client.get_multiplexed_async_connection().await?;
+
+    let replacement = match state.channel_id {
+        Some(channel_id) => serde_json::to_string(&VoiceSession { channel_id: channel_id.get(), since: now })?,
+        None => String::new(),
+    };
+
+    // Retry when the flush job restarts the session between the read and the swap.
+    for _ in 0..3 {
+        let previous: Option<String> = con.hget(SESSIONS_KEY, &user_id).await?;
+        let previous = previous.unwrap_or_default();
+        if !swap_session(&mut con, &user_id, &previous, &replacement).await? {
+            continue;
+        }
+        if let Ok(session) = serde_json::from_str::<VoiceSession>(&previous) {
+            credit_session(tracker, &user_id, session, now).await?;
+        }
+        return Ok(());
+    }
+    Err(format!("voice session of {} kept changing", user_id).into())
+}
+
+/// Credits everyone still in a voice channel, so members who stay past the deadline
+/// (or never leave) are counted without waiting for them to disconnect.
+pub async fn flush_voice_sessions(job: &JobContext) -> Result<(), Error> {
+    let tracker = VoiceTracker::from_job(job);
+    let now = Utc::now().timestamp();
+    let mut con = job.redis_client.get_multiplexed_async_connection().await?;
+    let sessions: Vec<(String, String)> = con.hgetall(SESSIONS_KEY).await?;
+
+    for (user_id, json) in sessions {
+        let Ok(session) = serde_json::from_str::<VoiceSession>(&json) else {
+            continue;
+        };
+        // Sessions that ended or moved since the read were credited by the voice handler.
+        let restarted = VoiceSession { since: now, ..session };
+        if !swap_session(&mut con, &user_id, &json, &serde_json::to_string(&restarted)?).await? {
+            continue;
+        }
+        credit_session(&tracker, &user_id, session, now).await?;
+    }
+    Ok(())
+}
+
+/// Rebuilds the sessions from the guild's current voice states when the bot
+/// (re)connects. Time spent while the bot was offline is not counted.
+pub async fn reset_voice_sessions(redis_client: &RedisClient, guild: &Guild) -> Result<(), Error> {
+    let now = Utc::now().timestamp();
+    let mut con = redis_client.get_multiplexed_async_connection().await?;
+    let _: () = con.del(SESSIONS_KEY).await?;
+
+    for (user_id, state) in &guild.voice_states {
+        if let Some(channel_id) = state.channel_id {
+            let session = VoiceSession { channel_id: channel_id.get(), since: now };
+            let _: () = con.hset(SESSIONS_KEY, user_id.to_string(), serde_json::to_string(&session)?).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlap_seconds() {
+        // Joined before the start, left in the middle.
+        assert_eq!(overlap_seconds(0, 600, 300, 3600), 300);
+        // Entirely inside the window.
+        assert_eq!(overlap_seconds(400, 1000, 300, 3600), 600);
+        // Stayed past the deadline.
+        assert_eq!(overlap_seconds(3000, 5000, 300, 3600), 600);
+        // Outside the window.
+        assert_eq!(overlap_seconds(0, 200, 300, 3600), 0);
+        assert_eq!(overlap_seconds(4000, 5000, 300, 3600), 0);
+    }
+}
@@ -0,0 +1,163 @@
+use chrono::{DateTime, Datelike, Duration, FixedOffset, TimeZone, Timelike};
+
+/// A five-field cron rule ("minute hour day-of-month month day-of-week"),
+/// evaluated in WIB. Supports `*`, numbers, lists (`1,15`), ranges (`1-5`),
+/// steps (`*/15`, `0-30/10`) and day names (`MON`..`SUN`, Sunday is 0 or 7).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronRule {
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    days_of_week: Vec<bool>,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+fn parse_value(token: &str, names: bool) -> Result<u32, String> {
+    if names {
+        if let Some(idx) = DAY_NAMES.iter().position(|d| d.eq_ignore_ascii_case(token)) {
+            return Ok(idx as u32);
+        }
+    }
+    token.parse::<u32>().map_err(|_| format!("`{}` is not a number", token))
+}
+
+fn parse_field(field: &str, min: u32, max: u32, names: bool) -> Result<(Vec<bool>, bool), String> {
+    let mut allowed = vec![false; max as usize + 1];
+    let restricted = field != "*";
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => (r, s.parse::<u32>().map_err(|_| format!("Invalid step in `{}`", part))?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(format!("Step cannot be 0 in `{}`", part));
+        }
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (parse_value(a, names)?, parse_value(b, names)?)
+        } else {
+            let v = parse_value(range, names)?;
+            (v, if part.contains('/') { max } else { v })
+        };
+
+        if start < min || end > max || start > end {
+            return Err(format!("`{}` is out of range {}-{}", part, min, max));
+        }
+
+        let mut v = start;
+        while v <= end {
+            allowed[v as usize] = true;
+            v += step;
+        }
+    }
+
+    Ok((allowed, restricted))
+}
+
+impl CronRule {
+    pub fn parse(rule: &str) -> Result<CronRule, String> {
+        let fields: Vec<&str> = rule.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err("Rule must have 5 fields: minute hour day-of-month month day-of-week (E.g: 0 19 * * FRI)".to_string());
+        }
+
+        let (minutes, _) = parse_field(fields[0], 0, 59, false)?;
+        let (hours, _) = parse_field(fields[1], 0, 23, false)?;
+        let (days_of_month, dom_restricted) = parse_field(fields[2], 1, 31, false)?;
+        let (months, _) = parse_field(fields[3], 1, 12, false)?;
+        let (mut days_of_week, dow_restricted) = parse_field(fields[4], 0, 7, true)?;
+        if days_of_week[7] {
+            days_of_week[0] = true;
+        }
+
+        Ok(CronRule { minutes, hours, days_of_month, months, days_of_week, dom_restricted, dow_restricted })
+    }
+
+    fn matches_day(&self, dt: &DateTime<FixedOffset>) -> bool {
+        if !self.months[dt.month() as usize] {
+            return false;
+        }
+        let dom = self.days_of_month[dt.day() as usize];
+        let dow = self.days_of_week[dt.weekday().num_days_from_sunday() as usize];
+        // Standard cron: when both day fields are restricted, either may match.
+        match (self.dom_restricted, self.dow_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+
+    /// First occurrence strictly after `after`, searching up to ~4 years ahead.
+    pub fn next_after(&self, after: DateTime<FixedOffset>) -> Option<DateTime<FixedOffset>> {
+        let tz = *after.offset();
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut day = tz.with_ymd_and_hms(start.year(), start.month(), start.day(), 0, 0, 0).single()?;
+
+        for _ in 0..(366 * 4) {
+            if self.matches_day(&day) {
+                for hour in 0..24u32 {
+                    if !self.hours[hour as usize] {
+                        continue;
+                    }
+                    for minute in 0..60u32 {
+                        if !self.minutes[minute as usize] {
+                            continue;
+                        }
+                        let candidate = day.with_hour(hour)?.with_minute(minute)?;
+                        if candidate >= start {
+                            return Some(candidate);
+                        }
+                    }
+                }
+            }
+            day += Duration::days(1);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wib(s: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(s).unwrap()
+    }
+
+    #[test]
+    fn test_weekly_friday_evening() {
+        let rule = CronRule::parse("0 19 * * FRI").unwrap();
+        // 2025-11-20 is a Thursday
+        let next = rule.next_after(wib("2025-11-20T10:00:00+07:00")).unwrap();
+        assert_eq!(next, wib("2025-11-21T19:00:00+07:00"));
+
+        let after_that = rule.next_after(next).unwrap();
+        assert_eq!(after_that, wib("2025-11-28T19:00:00+07:00"));
+    }
+
+    #[test]
+    fn test_steps_lists_and_ranges() {
+        let rule = CronRule::parse("*/30 9-10 1,15 * *").unwrap();
+        let next = rule.next_after(wib("2025-11-15T09:10:00+07:00")).unwrap();
+        assert_eq!(next, wib("2025-11-15T09:30:00+07:00"));
+
+        let next = rule.next_after(wib("2025-11-15T10:30:00+07:00")).unwrap();
+        assert_eq!(next, wib("2025-12-01T09:00:00+07:00"));
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(CronRule::parse("0 19 * *").is_err());
+        assert!(CronRule::parse("60 19 * * *").is_err());
+        assert!(CronRule::parse("0 19 * * FUNDAY").is_err());
+        assert!(CronRule::parse("*/0 19 * * *").is_err());
+    }
+}
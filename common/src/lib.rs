@@ -1,5 +1,11 @@
 use chrono::{TimeZone, NaiveDateTime, FixedOffset};
 
+pub mod cron;
+pub mod slots;
+
+pub use cron::CronRule;
+pub use slots::{free_slots, has_free_slot, parse_slots, slots_cell, slots_from_cell, slots_label, UNLIMITED_SLOTS};
+
 /// Quest status enum shared by both services.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum QuestStatus {
@@ -22,6 +28,12 @@ pub fn calculate_status(current_time: i64, start: &i64, end: &i64) -> QuestStatu
     }
 }
 
+/// Steps (1-based) of a multi-step quest that have no submission yet.
+/// Single-step quests have `total_steps` 0 or 1.
+pub fn remaining_steps(total_steps: i8, submitted: &[i8]) -> Vec<i8> {
+    (1..=total_steps.max(1)).filter(|step| !submitted.contains(step)).collect()
+}
+
 /// Parse a local WIB (Asia/Jakarta) string "YYYY-MM-DD HH:MM" into RFC3339 +07:00.
 pub fn parse_wib(input: &str) -> Result<String, String> {
     let naive = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M")
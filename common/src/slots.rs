@@ -0,0 +1,83 @@
+/// Written in the slots column for quests without a participant cap, and accepted
+/// (in any case) wherever slots are typed.
+pub const UNLIMITED_SLOTS: &str = "UNLIMITED";
+
+/// Parses a slot count typed by a quest giver: a whole number of at least 1, or
+/// `unlimited` (returned as `None`).
+pub fn parse_slots(input: &str) -> Result<Option<u32>, String> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case(UNLIMITED_SLOTS) {
+        return Ok(None);
+    }
+    if input.is_empty() {
+        return Err("Slots can't be empty. Enter a number such as 20, or `unlimited`.".to_string());
+    }
+    match input.parse::<i128>() {
+        Ok(n) if n < 0 => Err(format!("Slots can't be negative (got {}).", n)),
+        Ok(0) => Err("Slots must be at least 1. Use `unlimited` for no cap.".to_string()),
+        Ok(n) => u32::try_from(n)
+            .map(Some)
+            .map_err(|_| format!("Slots can be at most {}. Use `unlimited` for no cap.", u32::MAX)),
+        Err(_) => Err(format!("Slots must be a whole number or `unlimited` (got `{}`).", input)),
+    }
+}
+
+/// Reads the slots column of a Quests or Templates row. Unreadable cells count as 0
+/// (always full), so a broken cell never opens a quest to everyone.
+pub fn slots_from_cell(cell: &str) -> Option<u32> {
+    parse_slots(cell).unwrap_or(Some(0))
+}
+
+/// Value written to the slots column: the number, or `UNLIMITED`.
+pub fn slots_cell(slots: Option<u32>) -> String {
+    slots.map(|n| n.to_string()).unwrap_or_else(|| UNLIMITED_SLOTS.to_string())
+}
+
+/// Slots for display, with `∞` for unlimited quests.
+pub fn slots_label(slots: Option<u32>) -> String {
+    slots.map(|n| n.to_string()).unwrap_or_else(|| "∞".to_string())
+}
+
+/// Slots still open after `filled` participants; `None` when unlimited.
+pub fn free_slots(filled: u32, slots: Option<u32>) -> Option<u32> {
+    slots.map(|max| max.saturating_sub(filled))
+}
+
+/// Whether one more participant fits.
+pub fn has_free_slot(filled: u32, slots: Option<u32>) -> bool {
+    free_slots(filled, slots) != Some(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_slots() {
+        assert_eq!(parse_slots("200"), Ok(Some(200)));
+        assert_eq!(parse_slots(" 1 "), Ok(Some(1)));
+        assert_eq!(parse_slots("Unlimited"), Ok(None));
+        assert!(parse_slots("0").unwrap_err().contains("at least 1"));
+        assert!(parse_slots("-5").unwrap_err().contains("negative"));
+        assert!(parse_slots("ten").unwrap_err().contains("whole number"));
+        assert!(parse_slots("2.5").unwrap_err().contains("whole number"));
+        assert!(parse_slots("").unwrap_err().contains("empty"));
+        assert!(parse_slots("99999999999").unwrap_err().contains("at most"));
+    }
+
+    #[test]
+    fn test_slot_cells_and_counts() {
+        assert_eq!(slots_from_cell("150"), Some(150));
+        assert_eq!(slots_from_cell("UNLIMITED"), None);
+        // Old or broken cells are treated as full.
+        assert_eq!(slots_from_cell("-3"), Some(0));
+        assert_eq!(slots_from_cell(""), Some(0));
+        assert_eq!(slots_cell(None), "UNLIMITED");
+        assert_eq!(slots_label(None), "∞");
+
+        assert_eq!(free_slots(150, Some(200)), Some(50));
+        assert_eq!(free_slots(250, Some(200)), Some(0));
+        assert!(has_free_slot(10_000, None));
+        assert!(!has_free_slot(200, Some(200)));
+    }
+}
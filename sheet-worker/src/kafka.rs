@@ -0,0 +1,24 @@
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::util::Timeout;
+use std::time::Duration;
+use crate::models::EventMessage;
+
+/// Publishes an event on `quest.events`, the same topic the worker consumes,
+/// so jobs can trigger the regular event handlers (e.g. `CREATE_QUEST`).
+pub async fn produce_event(producer: &FutureProducer, event_type: &str, payload: &impl serde::Serialize) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let payload_json = serde_json::to_string(payload)?;
+    let event = EventMessage {
+        event_type: event_type.to_string(),
+        payload: payload_json,
+    };
+    let msg_json = serde_json::to_string(&event)?;
+
+    let record = FutureRecord::to("quest.events")
+        .payload(&msg_json)
+        .key(event_type);
+
+    producer.send(record, Timeout::After(Duration::from_secs(5))).await
+        .map_err(|(e, _)| e)?;
+
+    Ok(())
+}
@@ -1,9 +1,12 @@
 mod models;
 mod sheets;
+mod kafka;
+mod templates;
 
 use google_sheets4::{hyper, hyper_rustls, oauth2, Sheets};
 use rdkafka::config::ClientConfig;
 use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::producer::FutureProducer;
 use rdkafka::message::Message;
 use std::env;
 use crate::models::EventMessage;
@@ -51,6 +54,12 @@ async fn main() {
 
     consumer.subscribe(&["quest.events"]).expect("Subscription failed");
 
+    let producer: FutureProducer = ClientConfig::new()
+        .set("bootstrap.servers", &kafka_brokers)
+        .set("message.timeout.ms", "5000")
+        .create()
+        .expect("Producer creation error");
+
     println!("Worker Ready. Listening for events on 'quest.events'...");
 
     let hub_clone = hub.clone();
@@ -64,6 +73,33 @@ async fn main() {
         }
     });
 
+    let hub_clone = hub.clone();
+    let sheet_id_clone = sheet_id.clone();
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(300));
+        loop {
+            interval.tick().await;
+            templates::run_due_templates(&hub_clone, &sheet_id_clone, &producer).await;
+        }
+    });
+
+    let hub_clone = hub.clone();
+    let sheet_id_clone = sheet_id.clone();
+    let redis_clone = redis_client.clone();
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            if sheets::publish_scheduled_quests_job(&hub_clone, &sheet_id_clone).await {
+                if let Ok(mut con) = redis_clone.get_multiplexed_async_connection().await {
+                    let _: () = con.del("sheet_data_cache").await.unwrap_or_else(|e| eprintln!("Redis error: {}", e));
+                }
+            }
+        }
+    });
+
     loop {
         match consumer.recv().await {
             Err(e) => eprintln!("Kafka error: {}", e),
@@ -71,7 +107,7 @@ async fn main() {
                 if let Some(payload_result) = m.payload_view::<str>() {
                     if let Ok(text) = payload_result {
                         if let Ok(event) = serde_json::from_str::<EventMessage>(text) {
-                            sheets::process_event(&hub, &sheet_id, event).await;
+                            sheets::process_event(&hub, &sheet_id, &redis_client, event).await;
 
                             let mut con = redis_client.get_multiplexed_async_connection().await.unwrap();
                             let _: () = con.del("sheet_data_cache").await.unwrap_or_else(|e| eprintln!("Redis error: {}", e));
@@ -1,22 +1,46 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct EventMessage {
     pub event_type: String,
     pub payload: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct QuestPayload {
     pub quest_id: String,
     pub title: String,
     pub category: String,
-    pub slots: i8,
+    /// Participant cap; `None` (null) is unlimited.
+    pub slots: Option<u32>,
     pub deadline: String,
     pub organizer_name: String,
     pub description: String,
     pub schedule: String,
     pub platform: String,
+    #[serde(default)]
+    pub creator_id: String,
+    #[serde(default)]
+    pub cloned_from: Option<String>,
+    /// PUBLISHED or DRAFT; older gateways don't send it and always publish.
+    #[serde(default)]
+    pub status: String,
+    /// Members per team for party quests; 0 or 1 means solo.
+    #[serde(default)]
+    pub team_size: i8,
+    #[serde(default)]
+    pub prerequisites: Vec<String>,
+    #[serde(default)]
+    pub steps: i8,
+    #[serde(default)]
+    pub proof_kinds: Vec<String>,
+    /// Season id stamped by the gateway; empty when no season is running.
+    #[serde(default)]
+    pub season: String,
+    /// Written to publish_at (column M) so the gateway announces the quest; only
+    /// set for quests the worker creates itself.
+    #[serde(default)]
+    pub publish_at: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -26,6 +50,19 @@ pub struct RegistrationPayload {
     pub user_tag: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PartyMember {
+    pub user_id: String,
+    pub user_tag: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamRegistrationPayload {
+    pub quest_id: String,
+    pub team_id: String,
+    pub members: Vec<PartyMember>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct NewCommunityPayload {
     pub community_name: String,
@@ -37,6 +74,54 @@ pub struct ProofPayload {
     pub quest_id: String,
     pub user_id: String,
     pub proof_url: String,
+    #[serde(default)]
+    pub step: i8,
+    #[serde(default)]
+    pub total_steps: i8,
+    /// IMAGE, DOCUMENT, AUDIO, LINK or TEXT; older gateways only sent images.
+    #[serde(default = "default_proof_kind")]
+    pub proof_kind: String,
+}
+
+/// A participant checking in at a live quest.
+#[derive(Debug, Deserialize)]
+pub struct CheckInPayload {
+    pub quest_id: String,
+    pub user_id: String,
+}
+
+/// Binds a quest to a voice channel; an empty channel id unbinds it.
+#[derive(Debug, Deserialize)]
+pub struct VoiceBindingPayload {
+    pub quest_id: String,
+    pub voice_channel_id: String,
+    pub minutes: u32,
+}
+
+/// Participants an organizer marked as no-shows from `/roster`.
+#[derive(Debug, Deserialize)]
+pub struct NoShowPayload {
+    pub quest_id: String,
+    pub user_ids: Vec<String>,
+}
+
+/// A verifier's decision on one Submissions row (1-based `row_number`).
+#[derive(Debug, Deserialize)]
+pub struct ReviewPayload {
+    pub quest_id: String,
+    pub user_id: String,
+    pub row_number: usize,
+    /// APPROVED or REJECTED.
+    pub decision: String,
+    pub reviewer_id: String,
+    #[serde(default)]
+    pub reason: String,
+    #[serde(default)]
+    pub total_steps: i8,
+}
+
+fn default_proof_kind() -> String {
+    "IMAGE".to_string()
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,7 +129,8 @@ pub struct EditPayload {
     pub quest_id: String,
     pub title: String,
     pub description: String,
-    pub slots: i8,
+    /// Participant cap; `None` (null) is unlimited.
+    pub slots: Option<u32>,
     pub schedule: String,
     pub deadline: String,
     pub platform: String,
@@ -53,6 +139,42 @@ pub struct EditPayload {
 #[derive(Debug, Deserialize)]
 pub struct DeletePayload {
     pub quest_id: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublishPayload {
+    pub quest_id: String,
+    /// RFC3339 time to publish at; publish immediately when absent.
+    pub publish_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TemplatePayload {
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub category: String,
+    pub organizer_name: String,
+    /// Participant cap; `None` (null) is unlimited.
+    pub slots: Option<u32>,
+    pub platform: String,
+    pub duration_minutes: i64,
+    pub creator_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TemplateSchedulePayload {
+    pub name: String,
+    pub rule: String,
+    pub lead_hours: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TemplateStatusPayload {
+    pub name: String,
+    pub status: String,
 }
 
 #[cfg(test)]
@@ -76,7 +198,24 @@ mod tests {
 
         let payload: QuestPayload = serde_json::from_str(json).expect("Should deserialize");
         assert_eq!(payload.quest_id, "uuid-1");
-        assert_eq!(payload.slots, 10);
+        assert_eq!(payload.slots, Some(10));
+
+        let workshop: QuestPayload = serde_json::from_str(&json.replace("\"slots\": 10", "\"slots\": 200")).unwrap();
+        assert_eq!(workshop.slots, Some(200));
+        let unlimited: QuestPayload = serde_json::from_str(&json.replace("\"slots\": 10", "\"slots\": null")).unwrap();
+        assert_eq!(unlimited.slots, None);
+    }
+
+    #[test]
+    fn test_deserialize_team_registration() {
+        let json = r#"{
+            "quest_id": "uuid-1",
+            "team_id": "party-1",
+            "members": [{"user_id": "1", "user_tag": "a"}, {"user_id": "2", "user_tag": "b"}]
+        }"#;
+        let payload: TeamRegistrationPayload = serde_json::from_str(json).expect("Should deserialize");
+        assert_eq!(payload.members.len(), 2);
+        assert_eq!(payload.members[1].user_id, "2");
     }
 
     #[test]
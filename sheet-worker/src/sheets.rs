@@ -1,12 +1,15 @@
+use std::collections::HashSet;
+
 use google_sheets4::{hyper, hyper_rustls, Sheets, chrono};
-use google_sheets4::api::{ValueRange, BatchUpdateSpreadsheetRequest, Request, DeleteDimensionRequest, DimensionRange};
+use google_sheets4::api::{ValueRange, BatchUpdateValuesRequest, BatchUpdateSpreadsheetRequest, Request, DeleteDimensionRequest, DimensionRange};
 use serde_json::json;
-use common::normalize_name;
-use crate::models::{EventMessage, QuestPayload, RegistrationPayload, NewCommunityPayload, ProofPayload, EditPayload, DeletePayload};
+use redis::{AsyncCommands, Client as RedisClient};
+use common::{normalize_name, remaining_steps, slots_cell, slots_from_cell};
+use crate::models::{EventMessage, QuestPayload, RegistrationPayload, NewCommunityPayload, ProofPayload, EditPayload, DeletePayload, CheckInPayload, NoShowPayload, PublishPayload, ReviewPayload, TeamRegistrationPayload, TemplatePayload, TemplateSchedulePayload, TemplateStatusPayload, VoiceBindingPayload};
 
 pub type HubType = Sheets<hyper_rustls::HttpsConnector<hyper::client::HttpConnector>>;
 
-pub async fn process_event(hub: &HubType, spreadsheet_id: &str, event: EventMessage) {
+pub async fn process_event(hub: &HubType, spreadsheet_id: &str, redis_client: &RedisClient, event: EventMessage) {
     println!("Processing Event: {}", event.event_type);
     let now = chrono::Utc::now().to_rfc3339();
 
@@ -17,13 +20,24 @@ pub async fn process_event(hub: &HubType, spreadsheet_id: &str, event: EventMess
                     json!(data.quest_id),
                     json!(data.title),
                     json!(data.category),
-                    json!(data.slots),
+                    json!(slots_cell(data.slots)),
                     json!(data.organizer_name),
                     json!(data.schedule),
                     json!(data.platform),
                     json!(data.description),
                     json!(data.deadline),
                     json!(now),
+                    json!(data.cloned_from.unwrap_or_default()),
+                    json!(if data.status.is_empty() { "PUBLISHED".to_string() } else { data.status }),
+                    json!(data.publish_at.unwrap_or_default()),
+                    json!(if data.team_size > 1 { data.team_size.to_string() } else { String::new() }),
+                    json!(data.prerequisites.join(",")),
+                    json!(if data.steps > 1 { data.steps.to_string() } else { String::new() }),
+                    json!(data.proof_kinds.join(",")),
+                    json!(data.creator_id),
+                    json!(""),
+                    json!(""),
+                    json!(data.season),
                 ]];
                 append_to_sheet(hub, spreadsheet_id, "Quests!A1", values).await;
             } else {
@@ -52,8 +66,9 @@ pub async fn process_event(hub: &HubType, spreadsheet_id: &str, event: EventMess
                                 // column mapping (0-based):
                                 // 0: quest_id, 1: title, 2: category, 3: slots, 4: organizer_name,
                                 // 5: schedule, 6: platform, 7: description, 8: deadline, 9: created_at
+                                // (10: cloned_from and later columns are left untouched)
                                 existing[1] = data.title;
-                                existing[3] = data.slots.to_string();
+                                existing[3] = slots_cell(data.slots);
                                 existing[5] = data.schedule;
                                 existing[6] = data.platform;
                                 existing[7] = data.description;
@@ -84,7 +99,7 @@ pub async fn process_event(hub: &HubType, spreadsheet_id: &str, event: EventMess
         "DELETE_QUEST" => {
             if let Ok(data) = serde_json::from_str::<DeletePayload>(&event.payload) {
                 match delete_quest_cascade(hub, spreadsheet_id, &data.quest_id).await {
-                    Ok(_) => println!("✅ Cascade deleted quest {}", data.quest_id),
+                    Ok(_) => println!("✅ Cascade deleted quest {} (reason: {})", data.quest_id, data.reason),
                     Err(e) => eprintln!("Failed to cascade delete quest {}: {:?}", data.quest_id, e),
                 }
             } else {
@@ -105,6 +120,34 @@ pub async fn process_event(hub: &HubType, spreadsheet_id: &str, event: EventMess
             }
         },
 
+        "TAKE_QUEST_TEAM" => {
+            if let Ok(data) = serde_json::from_str::<TeamRegistrationPayload>(&event.payload) {
+                let result = match register_team(hub, spreadsheet_id, &data, &now).await {
+                    Ok(_) => {
+                        println!("✅ Team {} ({} members) registered for quest {}", data.team_id, data.members.len(), data.quest_id);
+                        "OK".to_string()
+                    }
+                    Err(e) => {
+                        eprintln!("Rejected team {} for quest {}: {}", data.team_id, data.quest_id, e);
+                        format!("REJECTED:{}", e)
+                    }
+                };
+                // The gateway waits on this key before telling the party it joined.
+                let key = format!("team_registration:{}:{}", data.quest_id, data.team_id);
+                match redis_client.get_multiplexed_async_connection().await {
+                    Ok(mut con) => {
+                        let stored: Result<(), _> = con.set_ex(&key, result, 3600).await;
+                        if let Err(e) = stored {
+                            eprintln!("Failed to record result for team {}: {}", data.team_id, e);
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to record result for team {}: {}", data.team_id, e),
+                }
+            } else {
+                eprintln!("Failed to parse TAKE_QUEST_TEAM payload");
+            }
+        },
+
         "RETAKE_QUEST" => {
             if let Ok(data) = serde_json::from_str::<RegistrationPayload>(&event.payload) {
                 update_participant_status(hub, spreadsheet_id, &data.quest_id, &data.user_id, "ON_PROGRESS").await;
@@ -153,14 +196,133 @@ pub async fn process_event(hub: &HubType, spreadsheet_id: &str, event: EventMess
 
         "SUBMIT_PROOF" => {
             if let Ok(data) = serde_json::from_str::<ProofPayload>(&event.payload) {
+                // Resubmissions are kept as extra rows; the attempt number counts per step.
+                let step = data.step.max(1);
+                let mut submitted = submitted_steps(hub, spreadsheet_id, &data.quest_id, &data.user_id).await;
+                let attempt = submitted.iter().filter(|s| **s == step).count() + 1;
                 let values = vec![vec![
                     json!(data.quest_id),
                     json!(data.user_id),
                     json!(data.proof_url),
                     json!(now),
+                    json!(step),
+                    json!(data.proof_kind),
+                    json!(attempt),
                 ]];
                 append_to_sheet(hub, spreadsheet_id, "Submissions!A1", values).await;
+                submitted.push(step);
+
+                // Proof is kept, but only a taken, unverified quest can become COMPLETED.
+                let status = participant_status(hub, spreadsheet_id, &data.quest_id, &data.user_id).await;
+                if !status.as_deref().is_some_and(|s| SUBMITTABLE_STATUSES.contains(&s)) {
+                    println!("Quest {} proof from {} recorded without a status change ({:?})", data.quest_id, data.user_id, status);
+                    return;
+                }
+
+                if data.total_steps > 1 {
+                    let remaining = remaining_steps(data.total_steps, &submitted);
+                    if !remaining.is_empty() {
+                        println!("Quest {} step {} submitted by {}, steps left: {:?}", data.quest_id, data.step, data.user_id, remaining);
+                        return;
+                    }
+                }
                 update_participant_status(hub, spreadsheet_id, &data.quest_id, &data.user_id, "COMPLETED").await;
+                set_team_status(hub, spreadsheet_id, &data.quest_id, &data.user_id, &["ON_PROGRESS", "CHECKED_IN"], "COMPLETED").await;
+            }
+        },
+
+        "CHECK_IN" => {
+            if let Ok(data) = serde_json::from_str::<CheckInPayload>(&event.payload) {
+                check_in(hub, spreadsheet_id, &data).await;
+            } else {
+                eprintln!("Failed to parse CHECK_IN payload");
+            }
+        },
+
+        "MARK_NO_SHOWS" => {
+            if let Ok(data) = serde_json::from_str::<NoShowPayload>(&event.payload) {
+                mark_no_shows(hub, spreadsheet_id, &data).await;
+            } else {
+                eprintln!("Failed to parse MARK_NO_SHOWS payload");
+            }
+        },
+
+        "REVIEW_SUBMISSION" => {
+            if let Ok(data) = serde_json::from_str::<ReviewPayload>(&event.payload) {
+                review_submission(hub, spreadsheet_id, &data, &now).await;
+            } else {
+                eprintln!("Failed to parse REVIEW_SUBMISSION payload");
+            }
+        },
+
+        "SAVE_TEMPLATE" => {
+            if let Ok(data) = serde_json::from_str::<TemplatePayload>(&event.payload) {
+                match find_row_index(hub, spreadsheet_id, "Templates!A:A", &data.name).await {
+                    Ok(Some(_)) => println!("Skipping duplicate template '{}'", data.name),
+                    _ => {
+                        let values = vec![vec![
+                            json!(data.name),
+                            json!(data.title),
+                            json!(data.category),
+                            json!(slots_cell(data.slots)),
+                            json!(data.organizer_name),
+                            json!(data.platform),
+                            json!(data.description),
+                            json!(data.duration_minutes),
+                            json!(data.creator_id),
+                            json!(""),
+                            json!(""),
+                            json!("UNSCHEDULED"),
+                            json!(""),
+                            json!(now),
+                        ]];
+                        append_to_sheet(hub, spreadsheet_id, "Templates!A1", values).await;
+                    }
+                }
+            } else {
+                eprintln!("Failed to parse SAVE_TEMPLATE payload");
+            }
+        },
+
+        "SCHEDULE_TEMPLATE" => {
+            if let Ok(data) = serde_json::from_str::<TemplateSchedulePayload>(&event.payload) {
+                // Occurrences are computed from J..M: rule, lead_hours, status, last_occurrence.
+                // Resetting last_occurrence to now means the first quest is the next match from now.
+                let wib_offset = chrono::FixedOffset::east_opt(7 * 3600).unwrap();
+                let now_wib = chrono::Utc::now().with_timezone(&wib_offset).to_rfc3339();
+                let values = vec![json!(data.rule), json!(data.lead_hours.to_string()), json!("ACTIVE"), json!(now_wib)];
+                update_template_cells(hub, spreadsheet_id, &data.name, "J", "M", values).await;
+            } else {
+                eprintln!("Failed to parse SCHEDULE_TEMPLATE payload");
+            }
+        },
+
+        "SET_TEMPLATE_STATUS" => {
+            if let Ok(data) = serde_json::from_str::<TemplateStatusPayload>(&event.payload) {
+                update_template_cells(hub, spreadsheet_id, &data.name, "L", "L", vec![json!(data.status)]).await;
+            } else {
+                eprintln!("Failed to parse SET_TEMPLATE_STATUS payload");
+            }
+        },
+
+        "PUBLISH_QUEST" => {
+            if let Ok(data) = serde_json::from_str::<PublishPayload>(&event.payload) {
+                let (status, publish_at) = match data.publish_at {
+                    Some(at) => ("SCHEDULED", at),
+                    None => ("PUBLISHED", now.clone()),
+                };
+                update_quest_cells(hub, spreadsheet_id, &data.quest_id, "L", "M", vec![json!(status), json!(publish_at)]).await;
+            } else {
+                eprintln!("Failed to parse PUBLISH_QUEST payload");
+            }
+        },
+
+        "SET_QUEST_VOICE" => {
+            if let Ok(data) = serde_json::from_str::<VoiceBindingPayload>(&event.payload) {
+                let minutes = if data.voice_channel_id.is_empty() { String::new() } else { data.minutes.to_string() };
+                update_quest_cells(hub, spreadsheet_id, &data.quest_id, "S", "T", vec![json!(data.voice_channel_id), json!(minutes)]).await;
+            } else {
+                eprintln!("Failed to parse SET_QUEST_VOICE payload");
             }
         },
 
@@ -174,10 +336,12 @@ pub async fn process_event(hub: &HubType, spreadsheet_id: &str, event: EventMess
     }
 }
 
+/// Appends rows as plain values. Rows carry user input (titles, text proofs, tags),
+/// so RAW keeps text like `=IMPORTXML(...)` from becoming a live formula.
 async fn append_to_sheet(hub: &HubType, spreadsheet_id: &str, range: &str, values: Vec<Vec<serde_json::Value>>) {
     let req = ValueRange { values: Some(values), ..Default::default() };
     let result = hub.spreadsheets().values_append(req, spreadsheet_id, range)
-        .value_input_option("USER_ENTERED")
+        .value_input_option("RAW")
         .doit().await;
 
     match result {
@@ -186,6 +350,327 @@ async fn append_to_sheet(hub: &HubType, spreadsheet_id: &str, range: &str, value
     }
 }
 
+async fn update_template_cells(hub: &HubType, spreadsheet_id: &str, name: &str, from_col: &str, to_col: &str, values: Vec<serde_json::Value>) {
+    match find_row_index(hub, spreadsheet_id, "Templates!A:A", name).await {
+        Ok(Some(row_number)) => {
+            let range = format!("Templates!{}{}:{}{}", from_col, row_number, to_col, row_number);
+            let req = ValueRange { values: Some(vec![values]), ..Default::default() };
+            match hub.spreadsheets().values_update(req, spreadsheet_id, &range)
+                .value_input_option("RAW")
+                .doit().await {
+                Ok(_) => println!("✅ Updated template '{}' ({})", name, range),
+                Err(e) => eprintln!("Failed to update template '{}': {:?}", name, e),
+            }
+        }
+        Ok(None) => eprintln!("Template '{}' not found", name),
+        Err(e) => eprintln!("Template lookup error: {:?}", e),
+    }
+}
+
+async fn update_quest_cells(hub: &HubType, spreadsheet_id: &str, quest_id: &str, from_col: &str, to_col: &str, values: Vec<serde_json::Value>) {
+    match find_row_index(hub, spreadsheet_id, "Quests!A:A", quest_id).await {
+        Ok(Some(row_number)) => {
+            let range = format!("Quests!{}{}:{}{}", from_col, row_number, to_col, row_number);
+            let req = ValueRange { values: Some(vec![values]), ..Default::default() };
+            match hub.spreadsheets().values_update(req, spreadsheet_id, &range)
+                .value_input_option("RAW")
+                .doit().await {
+                Ok(_) => println!("✅ Updated quest {} ({})", quest_id, range),
+                Err(e) => eprintln!("Failed to update quest {}: {:?}", quest_id, e),
+            }
+        }
+        Ok(None) => eprintln!("Quest {} not found", quest_id),
+        Err(e) => eprintln!("Quest lookup error: {:?}", e),
+    }
+}
+
+/// Registers every party member for a team quest in one go: either all members
+/// get a slot or, if the quest is full or someone already took it, nobody does.
+async fn register_team(hub: &HubType, spreadsheet_id: &str, data: &TeamRegistrationPayload, now: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let (_, q_range) = hub.spreadsheets().values_get(spreadsheet_id, "Quests!A:D").doit().await?;
+    let max_slots = q_range.values.unwrap_or_default().iter()
+        .find(|row| row.first().and_then(|v| v.as_str()) == Some(data.quest_id.as_str()))
+        .map(|row| slots_from_cell(row.get(3).and_then(|v| v.as_str()).unwrap_or("")))
+        .ok_or("quest not found")?;
+
+    // Participants column mapping (0-based):
+    // 0: quest_id, 1: user_id, 2: user_tag, 3: status, 4: timestamp, 5: team_id
+    let (_, p_range) = hub.spreadsheets().values_get(spreadsheet_id, "Participants!A:F").doit().await?;
+    let p_rows = p_range.values.unwrap_or_default();
+    let cell = |row: &Vec<serde_json::Value>, i: usize| row.get(i).and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+    let taken = p_rows.iter()
+        .filter(|row| cell(row, 0) == data.quest_id && cell(row, 3) != "DROPPED")
+        .count() as u32;
+    if let Some(max_slots) = max_slots {
+        if taken + data.members.len() as u32 > max_slots {
+            return Err(format!("only {} of {} slots left", max_slots.saturating_sub(taken), max_slots).into());
+        }
+    }
+
+    let mut rejoining = Vec::new();
+    let mut new_rows = Vec::new();
+    for member in &data.members {
+        let existing = p_rows.iter().position(|row| cell(row, 0) == data.quest_id && cell(row, 1) == member.user_id);
+        match existing {
+            Some(index) if cell(&p_rows[index], 3) != "DROPPED" => {
+                return Err(format!("{} already took this quest", member.user_tag).into());
+            }
+            Some(index) => rejoining.push(ValueRange {
+                range: Some(format!("Participants!D{}:F{}", index + 1, index + 1)),
+                values: Some(vec![vec![json!("ON_PROGRESS"), json!(now), json!(data.team_id)]]),
+                ..Default::default()
+            }),
+            None => new_rows.push(vec![
+                json!(data.quest_id),
+                json!(member.user_id),
+                json!(member.user_tag),
+                json!("ON_PROGRESS"),
+                json!(now),
+                json!(data.team_id),
+            ]),
+        }
+    }
+
+    if !rejoining.is_empty() {
+        let req = BatchUpdateValuesRequest {
+            data: Some(rejoining),
+            value_input_option: Some("RAW".to_string()),
+            ..Default::default()
+        };
+        hub.spreadsheets().values_batch_update(req, spreadsheet_id).doit().await?;
+    }
+    if !new_rows.is_empty() {
+        append_to_sheet(hub, spreadsheet_id, "Participants!A1", new_rows).await;
+    }
+    Ok(())
+}
+
+/// Steps a user has submitted proof for on a multi-step quest.
+async fn submitted_steps(hub: &HubType, spreadsheet_id: &str, quest_id: &str, user_id: &str) -> Vec<i8> {
+    // Submissions column mapping (0-based):
+    // 0: quest_id, 1: user_id, 2: proof (URL or text), 3: timestamp, 4: step, 5: proof_kind, 6: attempt
+    match hub.spreadsheets().values_get(spreadsheet_id, "Submissions!A:E").doit().await {
+        Ok((_, range)) => range.values.unwrap_or_default().iter()
+            .filter(|row| {
+                row.first().and_then(|v| v.as_str()) == Some(quest_id)
+                    && row.get(1).and_then(|v| v.as_str()) == Some(user_id)
+            })
+            .map(|row| row.get(4).and_then(|v| v.as_str()).and_then(|s| s.parse::<i8>().ok()).unwrap_or(1))
+            .collect(),
+        Err(e) => {
+            eprintln!("Failed to read Submissions sheet: {:?}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Marks a participant CHECKED_IN and writes the check-in time to column G, which
+/// keeps the attendance record after the status moves on to COMPLETED.
+async fn check_in(hub: &HubType, spreadsheet_id: &str, data: &CheckInPayload) {
+    let rows = match hub.spreadsheets().values_get(spreadsheet_id, "Participants!A:D").doit().await {
+        Ok((_, range)) => range.values.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Failed to read Participants sheet: {:?}", e);
+            return;
+        }
+    };
+    let cell = |row: &Vec<serde_json::Value>, i: usize| row.get(i).and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+    let Some(index) = rows.iter().position(|row| {
+        cell(row, 0) == data.quest_id && cell(row, 1) == data.user_id && cell(row, 3) == "ON_PROGRESS"
+    }) else {
+        println!("Ignoring check-in of {} on quest {}: not in progress", data.user_id, data.quest_id);
+        return;
+    };
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let row_number = index + 1;
+    let req = BatchUpdateValuesRequest {
+        data: Some(vec![
+            ValueRange {
+                range: Some(format!("Participants!D{}", row_number)),
+                values: Some(vec![vec![json!("CHECKED_IN")]]),
+                ..Default::default()
+            },
+            ValueRange {
+                range: Some(format!("Participants!G{}", row_number)),
+                values: Some(vec![vec![json!(now)]]),
+                ..Default::default()
+            },
+        ]),
+        value_input_option: Some("RAW".to_string()),
+        ..Default::default()
+    };
+    match hub.spreadsheets().values_batch_update(req, spreadsheet_id).doit().await {
+        Ok(_) => println!("✅ User {} checked in to quest {}", data.user_id, data.quest_id),
+        Err(e) => eprintln!("Failed to check in user {} on quest {}: {:?}", data.user_id, data.quest_id, e),
+    }
+}
+
+/// Marks the listed participants FAILED, skipping anyone who has submitted or
+/// dropped since the organizer looked at the roster.
+async fn mark_no_shows(hub: &HubType, spreadsheet_id: &str, data: &NoShowPayload) {
+    let rows = match hub.spreadsheets().values_get(spreadsheet_id, "Participants!A:D").doit().await {
+        Ok((_, range)) => range.values.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Failed to read Participants sheet: {:?}", e);
+            return;
+        }
+    };
+    let cell = |row: &Vec<serde_json::Value>, i: usize| row.get(i).and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+    // Anyone with a Submissions row for the quest showed up, even if a multi-step
+    // quest still has them ON_PROGRESS. Submissions: 0: quest_id, 1: user_id
+    let submitted: HashSet<String> = match hub.spreadsheets().values_get(spreadsheet_id, "Submissions!A:B").doit().await {
+        Ok((_, range)) => range.values.unwrap_or_default().iter()
+            .filter(|row| cell(row, 0) == data.quest_id)
+            .map(|row| cell(row, 1))
+            .collect(),
+        Err(e) => {
+            eprintln!("Failed to read Submissions sheet: {:?}", e);
+            return;
+        }
+    };
+
+    let updates: Vec<ValueRange> = rows.iter().enumerate()
+        .filter(|(_, row)| cell(row, 0) == data.quest_id && cell(row, 3) == "ON_PROGRESS" && data.user_ids.contains(&cell(row, 1)))
+        .filter(|(_, row)| !submitted.contains(&cell(row, 1)))
+        .map(|(index, _)| ValueRange {
+            range: Some(format!("Participants!D{}", index + 1)),
+            values: Some(vec![vec![json!("FAILED")]]),
+            ..Default::default()
+        })
+        .collect();
+    if updates.is_empty() {
+        return;
+    }
+
+    let count = updates.len();
+    let req = BatchUpdateValuesRequest {
+        data: Some(updates),
+        value_input_option: Some("RAW".to_string()),
+        ..Default::default()
+    };
+    match hub.spreadsheets().values_batch_update(req, spreadsheet_id).doit().await {
+        Ok(_) => println!("✅ Marked {} no-shows FAILED on quest {}", count, data.quest_id),
+        Err(e) => eprintln!("Failed to mark no-shows on quest {}: {:?}", data.quest_id, e),
+    }
+}
+
+/// Records a verifier's decision on a Submissions row (H..K). Approving the last
+/// unverified step marks the participant (and team) VERIFIED; rejecting puts them
+/// back to ON_PROGRESS so they can resubmit.
+async fn review_submission(hub: &HubType, spreadsheet_id: &str, data: &ReviewPayload, now: &str) {
+    // Submissions column mapping (0-based):
+    // 0: quest_id, 1: user_id, 2: proof, 3: timestamp, 4: step, 5: proof_kind, 6: attempt,
+    // 7: review_status, 8: reviewer_id, 9: reviewed_at, 10: review_reason
+    let rows = match hub.spreadsheets().values_get(spreadsheet_id, "Submissions!A:H").doit().await {
+        Ok((_, range)) => range.values.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Failed to read Submissions sheet: {:?}", e);
+            return;
+        }
+    };
+    let cell = |row: &Vec<serde_json::Value>, i: usize| row.get(i).and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+    let matches = rows.get(data.row_number.wrapping_sub(1))
+        .is_some_and(|row| cell(row, 0) == data.quest_id && cell(row, 1) == data.user_id);
+    if !matches {
+        eprintln!("Submission row {} does not belong to {} on quest {}, skipping review", data.row_number, data.user_id, data.quest_id);
+        return;
+    }
+
+    let range = format!("Submissions!H{}:K{}", data.row_number, data.row_number);
+    let req = ValueRange {
+        values: Some(vec![vec![json!(data.decision), json!(data.reviewer_id), json!(now), json!(data.reason)]]),
+        ..Default::default()
+    };
+    if let Err(e) = hub.spreadsheets().values_update(req, spreadsheet_id, &range).value_input_option("RAW").doit().await {
+        eprintln!("Failed to record review: {:?}", e);
+        return;
+    }
+
+    if data.decision == "APPROVED" {
+        let step_of = |row: &Vec<serde_json::Value>| cell(row, 4).parse::<i8>().unwrap_or(1);
+        let approved: Vec<i8> = rows.iter().enumerate()
+            .filter(|(i, row)| cell(row, 0) == data.quest_id && cell(row, 1) == data.user_id
+                && (cell(row, 7) == "APPROVED" || *i + 1 == data.row_number))
+            .map(|(_, row)| step_of(row))
+            .collect();
+        if remaining_steps(data.total_steps.max(1), &approved).is_empty() {
+            update_participant_status(hub, spreadsheet_id, &data.quest_id, &data.user_id, "VERIFIED").await;
+            set_team_status(hub, spreadsheet_id, &data.quest_id, &data.user_id, &["ON_PROGRESS", "CHECKED_IN", "COMPLETED"], "VERIFIED").await;
+        }
+    } else {
+        update_participant_status(hub, spreadsheet_id, &data.quest_id, &data.user_id, "ON_PROGRESS").await;
+        set_team_status(hub, spreadsheet_id, &data.quest_id, &data.user_id, &["COMPLETED"], "ON_PROGRESS").await;
+    }
+}
+
+/// Team quests share one outcome: moves the user's teammates from `from` to `to`,
+/// e.g. one proof marks the whole team COMPLETED, and its review VERIFIED.
+async fn set_team_status(hub: &HubType, spreadsheet_id: &str, quest_id: &str, user_id: &str, from: &[&str], to: &str) {
+    let rows = match hub.spreadsheets().values_get(spreadsheet_id, "Participants!A:F").doit().await {
+        Ok((_, range)) => range.values.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Failed to read Participants sheet: {:?}", e);
+            return;
+        }
+    };
+    let cell = |row: &Vec<serde_json::Value>, i: usize| row.get(i).and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+    let Some(team_id) = rows.iter()
+        .find(|row| cell(row, 0) == quest_id && cell(row, 1) == user_id)
+        .map(|row| cell(row, 5))
+        .filter(|team| !team.is_empty()) else {
+        return;
+    };
+
+    let updates: Vec<ValueRange> = rows.iter().enumerate()
+        .filter(|(_, row)| cell(row, 0) == quest_id && cell(row, 5) == team_id && from.contains(&cell(row, 3).as_str()))
+        .map(|(index, _)| ValueRange {
+            range: Some(format!("Participants!D{}", index + 1)),
+            values: Some(vec![vec![json!(to)]]),
+            ..Default::default()
+        })
+        .collect();
+    if updates.is_empty() {
+        return;
+    }
+
+    let req = BatchUpdateValuesRequest {
+        data: Some(updates),
+        value_input_option: Some("RAW".to_string()),
+        ..Default::default()
+    };
+    match hub.spreadsheets().values_batch_update(req, spreadsheet_id).doit().await {
+        Ok(_) => println!("✅ Set team {} on quest {} to {}", team_id, quest_id, to),
+        Err(e) => eprintln!("Failed to update team {}: {:?}", team_id, e),
+    }
+}
+
+/// Participant statuses a proof submission may move to COMPLETED.
+const SUBMITTABLE_STATUSES: [&str; 3] = ["ON_PROGRESS", "CHECKED_IN", "COMPLETED"];
+
+/// Current status of the user's row for a quest, `None` when they never took it
+/// or the sheet can't be read.
+async fn participant_status(hub: &HubType, spreadsheet_id: &str, quest_id: &str, user_id: &str) -> Option<String> {
+    // Participants column mapping (0-based): 0: quest_id, 1: user_id, 3: status
+    match hub.spreadsheets().values_get(spreadsheet_id, "Participants!A:D").doit().await {
+        Ok((_, range)) => range.values.unwrap_or_default().iter()
+            .find(|row| {
+                row.first().and_then(|v| v.as_str()) == Some(quest_id)
+                    && row.get(1).and_then(|v| v.as_str()) == Some(user_id)
+            })
+            .map(|row| row.get(3).and_then(|v| v.as_str()).unwrap_or("").to_string()),
+        Err(e) => {
+            eprintln!("Failed to read Participants sheet: {:?}", e);
+            None
+        }
+    }
+}
+
 async fn update_participant_status(hub: &HubType, spreadsheet_id: &str, quest_id: &str, user_id: &str, new_status: &str) {
     let read_result = hub.spreadsheets().values_get(spreadsheet_id, "Participants!A:D").doit().await;
 
@@ -250,7 +735,7 @@ pub async fn check_deadlines_job(hub: &HubType, spreadsheet_id: &str) {
                 let q_id = row[0].as_str().unwrap_or("");
                 let status = row[3].as_str().unwrap_or("");
 
-                if status == "ON_PROGRESS" {
+                if status == "ON_PROGRESS" || status == "CHECKED_IN" {
                     if let Some(deadline) = quest_deadlines.get(q_id) {
                         if now > *deadline {
                             println!("Quest {} expired for row {}. Marking FAILED.", q_id, index+1);
@@ -274,6 +759,45 @@ pub async fn check_deadlines_job(hub: &HubType, spreadsheet_id: &str) {
     println!("Deadline Check Finished.");
 }
 
+/// Flips SCHEDULED quests to PUBLISHED once their publish time (column M) has passed.
+/// The gateway posts the announcement; this only makes the quest visible.
+pub async fn publish_scheduled_quests_job(hub: &HubType, spreadsheet_id: &str) -> bool {
+    let rows = match hub.spreadsheets().values_get(spreadsheet_id, "Quests!A:M").doit().await {
+        Ok((_, range)) => range.values.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Failed to read Quests sheet: {:?}", e);
+            return false;
+        }
+    };
+    let now = chrono::Utc::now();
+    let mut changed = false;
+
+    for (index, row) in rows.iter().enumerate().skip(1) {
+        let cell = |i: usize| row.get(i).and_then(|v| v.as_str()).unwrap_or("");
+        if cell(11) != "SCHEDULED" {
+            continue;
+        }
+        let Ok(publish_at) = chrono::DateTime::parse_from_rfc3339(cell(12)) else {
+            continue;
+        };
+        if publish_at > now {
+            continue;
+        }
+
+        let req = ValueRange { values: Some(vec![vec![json!("PUBLISHED")]]), ..Default::default() };
+        match hub.spreadsheets().values_update(req, spreadsheet_id, &format!("Quests!L{}", index + 1))
+            .value_input_option("RAW")
+            .doit().await {
+            Ok(_) => {
+                println!("✅ Published scheduled quest {}", cell(0));
+                changed = true;
+            }
+            Err(e) => eprintln!("Failed to publish quest {}: {:?}", cell(0), e),
+        }
+    }
+    changed
+}
+
 async fn find_row_index(hub: &HubType, spreadsheet_id: &str, range: &str, quest_id: &str) -> Result<Option<usize>, Box<dyn std::error::Error + Send + Sync>> {
     let res = hub.spreadsheets().values_get(spreadsheet_id, range).doit().await?;
     if let Some(vr) = res.1.values {
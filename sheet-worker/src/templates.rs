@@ -0,0 +1,141 @@
+use google_sheets4::api::ValueRange;
+use google_sheets4::chrono::{self, DateTime, Duration, FixedOffset};
+use rdkafka::producer::FutureProducer;
+use serde_json::json;
+use common::{slots_from_cell, CronRule};
+
+use crate::kafka::produce_event;
+use crate::models::QuestPayload;
+use crate::sheets::HubType;
+
+// Templates tab column mapping (0-based):
+// 0: name, 1: title, 2: category, 3: slots, 4: organizer_name, 5: platform,
+// 6: description, 7: duration_minutes, 8: creator_id, 9: rule, 10: lead_hours,
+// 11: status (UNSCHEDULED/ACTIVE/PAUSED), 12: last_occurrence, 13: created_at
+
+/// Next occurrence after `last` whose quest would not already be over at `now`.
+/// Occurrences missed while the worker was down are skipped, not back-filled.
+pub fn next_occurrence(
+    rule: &CronRule,
+    last: DateTime<FixedOffset>,
+    duration: Duration,
+    now: DateTime<FixedOffset>,
+) -> Option<DateTime<FixedOffset>> {
+    let mut next = rule.next_after(last)?;
+    while next + duration < now {
+        next = rule.next_after(next)?;
+    }
+    Some(next)
+}
+
+/// Records the next occurrence of every active template whose occurrence is within
+/// its lead time, then emits `CREATE_QUEST` for it. The occurrence is written first
+/// so a failed write can't create the same quest again on the next tick.
+pub async fn run_due_templates(hub: &HubType, spreadsheet_id: &str, producer: &FutureProducer) {
+    let rows = match hub.spreadsheets().values_get(spreadsheet_id, "Templates!A:N").doit().await {
+        Ok((_, range)) => range.values.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Failed to read Templates sheet: {:?}", e);
+            return;
+        }
+    };
+
+    let wib_offset = FixedOffset::east_opt(7 * 3600).unwrap();
+    let now = chrono::Utc::now().with_timezone(&wib_offset);
+
+    for (index, row) in rows.iter().enumerate().skip(1) {
+        let cell = |i: usize| row.get(i).and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+        if cell(11) != "ACTIVE" {
+            continue;
+        }
+
+        let name = cell(0);
+        let rule = match CronRule::parse(&cell(9)) {
+            Ok(rule) => rule,
+            Err(e) => {
+                eprintln!("Template `{}` has an invalid rule: {}", name, e);
+                continue;
+            }
+        };
+
+        let duration = Duration::minutes(cell(7).parse::<i64>().unwrap_or(0));
+        let lead = Duration::hours(cell(10).parse::<i64>().unwrap_or(24));
+        let last = DateTime::parse_from_rfc3339(&cell(12)).unwrap_or(now);
+
+        let Some(occurrence) = next_occurrence(&rule, last, duration, now) else {
+            continue;
+        };
+
+        if now + lead < occurrence {
+            continue;
+        }
+
+        let row_number = index + 1;
+        if let Err(e) = write_last_occurrence(hub, spreadsheet_id, row_number, &occurrence.to_rfc3339()).await {
+            eprintln!("Failed to record occurrence for template `{}`: {:?}", name, e);
+            continue;
+        }
+
+        let payload = QuestPayload {
+            quest_id: uuid::Uuid::new_v4().to_string(),
+            title: cell(1),
+            category: cell(2),
+            slots: slots_from_cell(&cell(3)),
+            organizer_name: cell(4),
+            platform: cell(5),
+            description: cell(6),
+            schedule: occurrence.to_rfc3339(),
+            deadline: (occurrence + duration).to_rfc3339(),
+            creator_id: cell(8),
+            cloned_from: None,
+            status: "PUBLISHED".to_string(),
+            team_size: 0,
+            prerequisites: Vec::new(),
+            steps: 0,
+            proof_kinds: Vec::new(),
+            // Templates can't see the guild config; the gateway places these quests by created_at.
+            season: String::new(),
+            // The gateway announces quests once their publish_at has passed.
+            publish_at: Some(now.to_rfc3339()),
+        };
+
+        match produce_event(producer, "CREATE_QUEST", &payload).await {
+            Ok(_) => println!("✅ Template `{}` created quest {} for {}", name, payload.quest_id, occurrence),
+            Err(e) => {
+                eprintln!("Failed to emit quest for template `{}`: {:?}", name, e);
+                // Put the previous occurrence back so the quest is retried on the next tick.
+                if let Err(e) = write_last_occurrence(hub, spreadsheet_id, row_number, &cell(12)).await {
+                    eprintln!("Failed to roll back occurrence for template `{}`: {:?}", name, e);
+                }
+            }
+        }
+    }
+}
+
+/// Writes a template's last occurrence (column M).
+async fn write_last_occurrence(hub: &HubType, spreadsheet_id: &str, row_number: usize, value: &str) -> Result<(), google_sheets4::Error> {
+    let req = ValueRange {
+        values: Some(vec![vec![json!(value)]]),
+        ..Default::default()
+    };
+    hub.spreadsheets().values_update(req, spreadsheet_id, &format!("Templates!M{}", row_number))
+        .value_input_option("RAW")
+        .doit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_occurrence_skips_missed_runs() {
+        let rule = CronRule::parse("0 19 * * FRI").unwrap();
+        let last = DateTime::parse_from_rfc3339("2025-11-07T19:00:00+07:00").unwrap();
+        let now = DateTime::parse_from_rfc3339("2025-11-20T10:00:00+07:00").unwrap();
+
+        let next = next_occurrence(&rule, last, Duration::hours(2), now).unwrap();
+        assert_eq!(next, DateTime::parse_from_rfc3339("2025-11-21T19:00:00+07:00").unwrap());
+    }
+}